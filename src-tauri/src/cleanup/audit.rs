@@ -0,0 +1,434 @@
+// src-tauri/src/cleanup/audit.rs
+use crate::error::{CleanerError, Result};
+use crate::paths::path_to_bytes;
+use crate::scanner::RuleEngine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const AUDIT_FILE: &str = "audit.jsonl";
+/// `prev_hash` of the first entry.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Serializes appends within the process; other processes (the CLI) are
+/// kept out by a file lock.
+static APPEND_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AuditAction {
+    Delete,
+    Trash,
+    SecureDelete,
+    Quarantine,
+    /// In-place change, e.g. history rows removed from a browser database.
+    Modify,
+    Restore,
+    StartupChange,
+    /// A torn last entry, left by a crash mid-write, was cut off the log;
+    /// `size` is the number of bytes dropped.
+    Repair,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AuditOutcome {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+/// One line of the audit log without its own hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub user: String,
+    pub operation_id: Option<i64>,
+    pub action: AuditAction,
+    /// Display form of the path.
+    pub path: String,
+    /// Exact bytes of a path that is not valid UTF-8; absent otherwise, so
+    /// entries written before it existed still verify.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_path: Option<Vec<u8>>,
+    pub size: Option<u64>,
+    /// BLAKE3 of the file contents before the action; directories have none.
+    pub content_hash: Option<String>,
+    pub rule_id: Option<String>,
+    pub outcome: AuditOutcome,
+    pub error: Option<String>,
+    pub prev_hash: String,
+}
+
+/// An audit line: the record plus BLAKE3 over the record, which includes
+/// the previous line's hash, so changing any line breaks the chain after it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    #[serde(flatten)]
+    pub record: AuditRecord,
+    pub entry_hash: String,
+}
+
+/// What is about to happen to a path. Size and content hash are captured on
+/// creation, i.e. before the action runs; the rule is added by callers that
+/// have a `RuleEngine` at hand.
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    pub operation_id: Option<i64>,
+    pub action: AuditAction,
+    pub path: String,
+    pub raw_path: Option<Vec<u8>>,
+    pub size: Option<u64>,
+    pub content_hash: Option<String>,
+    pub rule_id: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(action: AuditAction, path: &Path) -> Self {
+        let mut event = Self::unhashed(action, path);
+        if event.size.is_some() && action != AuditAction::Restore {
+            event.content_hash = hash_file(path).ok();
+        }
+        event
+    }
+
+    /// Like `new`, but leaves the content hash to the caller, e.g. when a
+    /// backup is about to read and hash the file anyway.
+    pub fn unhashed(action: AuditAction, path: &Path) -> Self {
+        let mut event = Self::removed(action, path);
+        event.size = fs::symlink_metadata(path).ok().filter(|m| m.is_file()).map(|m| m.len());
+        event
+    }
+
+    /// An event for a path that is already gone; nothing is read from disk,
+    /// so set the size from what was recorded before.
+    pub fn removed(action: AuditAction, path: &Path) -> Self {
+        Self {
+            operation_id: None,
+            action,
+            path: path.to_string_lossy().to_string(),
+            raw_path: path.to_str().is_none().then(|| path_to_bytes(path).into_owned()),
+            size: None,
+            content_hash: None,
+            rule_id: None,
+        }
+    }
+
+    /// Records the id of the rule in `rules` that matches the path.
+    pub fn rule(mut self, rules: &RuleEngine, path: &Path) -> Self {
+        self.rule_id = fs::symlink_metadata(path)
+            .ok()
+            .and_then(|m| rules.match_rule(path, &m).map(|rule| rule.id.clone()));
+        self
+    }
+
+    /// An event for something that is not a file, e.g. a startup entry.
+    pub fn named(action: AuditAction, name: &str) -> Self {
+        Self {
+            operation_id: None,
+            action,
+            path: name.to_string(),
+            raw_path: None,
+            size: None,
+            content_hash: None,
+            rule_id: None,
+        }
+    }
+
+    pub fn operation(mut self, operation_id: i64) -> Self {
+        self.operation_id = Some(operation_id);
+        self
+    }
+
+    pub fn size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditVerification {
+    pub entries: u64,
+    pub valid: bool,
+    /// 1-based line of the first entry that does not check out.
+    pub first_invalid_line: Option<u64>,
+    pub problem: Option<String>,
+    pub last_hash: Option<String>,
+    /// 1-based lines of the entries recording a torn entry cut off the end.
+    pub repairs: Vec<u64>,
+}
+
+/// Append-only, hash-chained JSON-lines log of every destructive action,
+/// kept at `<app data>/audit.jsonl`.
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(app_data_dir: &Path) -> Self {
+        Self {
+            path: app_data_dir.join(AUDIT_FILE),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends an entry for an action that already happened.
+    pub fn record(&self, event: AuditEvent, outcome: AuditOutcome, error: Option<&str>) -> Result<()> {
+        self.append(event, outcome, error).map(|_| ())
+    }
+
+    /// Appends an entry chained to the last one. A last line cut short by a
+    /// crash is dropped first, and the drop is logged as a `Repair` entry.
+    pub fn append(&self, event: AuditEvent, outcome: AuditOutcome, error: Option<&str>) -> Result<AuditEntry> {
+        let _guard = APPEND_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().read(true).append(true).create(true).open(&self.path)?;
+        #[cfg(unix)]
+        let _lock = FileLock::exclusive(&file)?;
+
+        let (mut seq, mut prev_hash) = (1, GENESIS_HASH.to_string());
+        let mut dropped = 0;
+        while let Some(last) = last_line(&mut file)? {
+            match serde_json::from_str::<AuditEntry>(&last.text) {
+                Ok(entry) => {
+                    if !last.terminated {
+                        file.write_all(b"\n")?;
+                    }
+                    (seq, prev_hash) = (entry.record.seq + 1, entry.entry_hash);
+                    break;
+                }
+                // Entries are written together with their newline, so only an
+                // unterminated line can be a torn write
+                Err(_) if !last.terminated => {
+                    let len = file.seek(SeekFrom::End(0))?;
+                    file.set_len(last.offset)?;
+                    dropped += len - last.offset;
+                }
+                Err(e) => return Err(CleanerError::Corrupted(format!("last audit entry: {}", e))),
+            }
+        }
+
+        if dropped > 0 {
+            let repair = AuditEvent::named(AuditAction::Repair, &self.path.to_string_lossy()).size(dropped);
+            let entry = write_entry(&mut file, seq, prev_hash, repair, AuditOutcome::Succeeded, None)?;
+            (seq, prev_hash) = (seq + 1, entry.entry_hash);
+        }
+        write_entry(&mut file, seq, prev_hash, event, outcome, error)
+    }
+
+    /// Walks the whole chain. Any edited, inserted, removed or reordered
+    /// line is reported; lines cut off the end can only be caught by
+    /// comparing `last_hash` with a copy kept elsewhere. A torn last line
+    /// shows up as invalid until the next append repairs it.
+    pub fn verify(&self) -> Result<AuditVerification> {
+        let mut result = AuditVerification {
+            entries: 0,
+            valid: true,
+            first_invalid_line: None,
+            problem: None,
+            last_hash: None,
+            repairs: Vec::new(),
+        };
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(result),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut prev_hash = GENESIS_HASH.to_string();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            let problem = match check_line(&line, index as u64 + 1, &prev_hash) {
+                Ok(entry) => {
+                    if entry.record.action == AuditAction::Repair {
+                        result.repairs.push(index as u64 + 1);
+                    }
+                    prev_hash = entry.entry_hash;
+                    result.entries += 1;
+                    continue;
+                }
+                Err(problem) => problem,
+            };
+            result.valid = false;
+            result.first_invalid_line = Some(index as u64 + 1);
+            result.problem = Some(problem);
+            return Ok(result);
+        }
+
+        result.last_hash = (result.entries > 0).then_some(prev_hash);
+        Ok(result)
+    }
+
+    /// Copies the lines logged between `from` and `to` (inclusive) to `out`
+    /// unchanged, so the export can be checked against the original chain.
+    pub fn export(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>, out: &mut dyn Write) -> Result<u64> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut exported = 0;
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            let entry: AuditEntry = serde_json::from_str(&line)?;
+            let timestamp = entry.record.timestamp;
+            if from.map(|f| timestamp < f).unwrap_or(false) || to.map(|t| timestamp > t).unwrap_or(false) {
+                continue;
+            }
+            writeln!(out, "{}", line)?;
+            exported += 1;
+        }
+        Ok(exported)
+    }
+}
+
+fn check_line(line: &str, expected_seq: u64, prev_hash: &str) -> std::result::Result<AuditEntry, String> {
+    let entry: AuditEntry = serde_json::from_str(line).map_err(|e| format!("not a valid entry: {}", e))?;
+    if entry.record.seq != expected_seq {
+        return Err(format!("sequence {} where {} was expected", entry.record.seq, expected_seq));
+    }
+    if entry.record.prev_hash != prev_hash {
+        return Err("does not chain to the previous entry".to_string());
+    }
+    if record_hash(&entry.record).map_err(|e| e.to_string())? != entry.entry_hash {
+        return Err("contents do not match the entry hash".to_string());
+    }
+    // Fields the hash does not cover (extra keys, reformatting) count too
+    if serde_json::to_string(&entry).map_err(|e| e.to_string())? != line {
+        return Err("line was rewritten".to_string());
+    }
+    Ok(entry)
+}
+
+fn write_entry(
+    file: &mut File,
+    seq: u64,
+    prev_hash: String,
+    event: AuditEvent,
+    outcome: AuditOutcome,
+    error: Option<&str>,
+) -> Result<AuditEntry> {
+    let record = AuditRecord {
+        seq,
+        timestamp: Utc::now(),
+        user: current_user(),
+        operation_id: event.operation_id,
+        action: event.action,
+        path: event.path,
+        raw_path: event.raw_path,
+        size: event.size,
+        content_hash: event.content_hash,
+        rule_id: event.rule_id,
+        outcome,
+        error: error.map(String::from),
+        prev_hash,
+    };
+    let entry = AuditEntry {
+        entry_hash: record_hash(&record)?,
+        record,
+    };
+
+    let mut line = serde_json::to_string(&entry)?;
+    line.push('\n');
+    file.write_all(line.as_bytes())?;
+    file.sync_data()?;
+    Ok(entry)
+}
+
+fn record_hash(record: &AuditRecord) -> Result<String> {
+    Ok(blake3::hash(&serde_json::to_vec(record)?).to_hex().to_string())
+}
+
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+struct LastLine {
+    /// Byte offset of the start of the line.
+    offset: u64,
+    text: String,
+    /// Whether the line ends in a newline.
+    terminated: bool,
+}
+
+/// The last non-empty line, read backwards from the end of the file.
+fn last_line(file: &mut File) -> Result<Option<LastLine>> {
+    const CHUNK: u64 = 8 * 1024;
+    let len = file.seek(SeekFrom::End(0))?;
+    let mut tail = Vec::new();
+    let mut pos = len;
+
+    while pos > 0 {
+        let start = pos.saturating_sub(CHUNK);
+        let mut chunk = vec![0u8; (pos - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        pos = start;
+
+        let trimmed = tail.strip_suffix(b"\n").unwrap_or(&tail);
+        if let Some(newline) = trimmed.iter().rposition(|&b| b == b'\n') {
+            return Ok(Some(LastLine {
+                offset: pos + newline as u64 + 1,
+                text: String::from_utf8_lossy(&trimmed[newline + 1..]).to_string(),
+                terminated: trimmed.len() < tail.len(),
+            }));
+        }
+    }
+
+    let trimmed = tail.strip_suffix(b"\n").unwrap_or(&tail);
+    Ok((!trimmed.is_empty()).then(|| LastLine {
+        offset: 0,
+        text: String::from_utf8_lossy(trimmed).to_string(),
+        terminated: trimmed.len() < tail.len(),
+    }))
+}
+
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| {
+            #[cfg(unix)]
+            {
+                format!("uid {}", unsafe { libc::getuid() })
+            }
+            #[cfg(not(unix))]
+            {
+                "unknown".to_string()
+            }
+        })
+}
+
+#[cfg(unix)]
+struct FileLock(std::os::unix::io::RawFd);
+
+#[cfg(unix)]
+impl FileLock {
+    /// Held until dropped; the descriptor has to outlive the lock.
+    fn exclusive(file: &File) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+        let fd = file.as_raw_fd();
+        if unsafe { libc::flock(fd, libc::LOCK_EX) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self(fd))
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        unsafe { libc::flock(self.0, libc::LOCK_UN) };
+    }
+}
@@ -0,0 +1,535 @@
+// src-tauri/src/cleanup/backup_store.rs
+use crate::error::{CleanerError, Result};
+use crate::paths::{path_to_bytes, serde_path, RawPath, SqlPath};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// zstd level used unless the store is built `with_compression(None)`.
+pub const DEFAULT_COMPRESSION_LEVEL: i32 = 3;
+
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Keeps temporary object names unique when several backups run at once.
+static INCOMING: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum EntryKind {
+    File,
+    Directory,
+    Symlink,
+}
+
+/// One file, directory or symlink of a backed-up tree. The root of the
+/// tree has an empty `relative_path`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    #[serde(with = "serde_path")]
+    pub relative_path: PathBuf,
+    pub kind: EntryKind,
+    /// BLAKE3 of the file contents; only set for files.
+    pub object_hash: Option<String>,
+    pub size: u64,
+    pub mode: u32,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub modified: Option<DateTime<Utc>>,
+    #[serde(with = "serde_path::option")]
+    pub link_target: Option<PathBuf>,
+    pub xattrs: Vec<(String, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupStats {
+    pub files: u64,
+    pub bytes: u64,
+    /// Bytes written to the store; lower than `bytes` through dedup and compression.
+    pub stored_bytes: u64,
+    pub deduplicated_bytes: u64,
+}
+
+/// Content-addressed storage for restore points. File contents live once
+/// under `backups/objects/<aa>/<hash>`, however many restore points refer
+/// to them; the tree layout and metadata of each backup are kept in the
+/// `backup_entries` table, keyed by journal file.
+pub struct BackupStore {
+    objects_dir: PathBuf,
+    compression_level: Option<i32>,
+}
+
+impl BackupStore {
+    pub fn new(app_data_dir: &Path) -> Self {
+        Self {
+            objects_dir: app_data_dir.join("backups").join("objects"),
+            compression_level: Some(DEFAULT_COMPRESSION_LEVEL),
+        }
+    }
+
+    /// `None` stores objects uncompressed.
+    pub fn with_compression(mut self, level: Option<i32>) -> Self {
+        self.compression_level = level;
+        self
+    }
+
+    /// Backs up `path` (file, symlink or whole directory) for a journal file
+    /// and returns the hash identifying the backup: the content hash for a
+    /// file, a hash over the manifest for a directory.
+    pub fn backup(&self, conn: &Connection, journal_file_id: i64, path: &Path) -> Result<(String, BackupStats)> {
+        let mut stats = BackupStats::default();
+        let mut entries = Vec::new();
+
+        for entry in walkdir::WalkDir::new(path).follow_links(false).sort_by_file_name() {
+            let entry = entry.map_err(|e| CleanerError::System(e.to_string()))?;
+            let metadata = entry.path().symlink_metadata()?;
+            let relative = entry.path().strip_prefix(path).unwrap_or(Path::new("")).to_path_buf();
+            let file_type = metadata.file_type();
+
+            let (kind, object_hash, link_target) = if file_type.is_dir() {
+                (EntryKind::Directory, None, None)
+            } else if file_type.is_symlink() {
+                (EntryKind::Symlink, None, Some(fs::read_link(entry.path())?))
+            } else if !file_type.is_file() {
+                // Opening a FIFO blocks and a socket fails; neither holds data to keep
+                tracing::warn!("Not backing up special file {:?}", entry.path());
+                continue;
+            } else {
+                let (hash, stored, deduplicated) = self.put_object(conn, entry.path())?;
+                stats.files += 1;
+                stats.bytes += metadata.len();
+                stats.stored_bytes += stored;
+                if deduplicated {
+                    stats.deduplicated_bytes += metadata.len();
+                }
+                (EntryKind::File, Some(hash), None)
+            };
+
+            entries.push(BackupEntry {
+                relative_path: relative,
+                kind,
+                object_hash,
+                size: if kind == EntryKind::File { metadata.len() } else { 0 },
+                mode: file_mode(&metadata),
+                uid: owner(&metadata).map(|o| o.0),
+                gid: owner(&metadata).map(|o| o.1),
+                modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+                link_target,
+                xattrs: read_xattrs(entry.path()),
+            });
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        // A resumed operation may have left a partial manifest behind
+        tx.execute("DELETE FROM backup_entries WHERE journal_file_id = ?1", [journal_file_id])?;
+        for entry in &entries {
+            tx.execute(
+                "INSERT INTO backup_entries (journal_file_id, relative_path, kind, object_hash, size, mode, uid, gid,
+                                             modified, link_target, xattrs)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    journal_file_id,
+                    SqlPath(&entry.relative_path),
+                    kind_str(entry.kind),
+                    entry.object_hash,
+                    entry.size as i64,
+                    entry.mode,
+                    entry.uid,
+                    entry.gid,
+                    entry.modified.map(|t| t.to_rfc3339()),
+                    entry.link_target.as_deref().map(SqlPath),
+                    (!entry.xattrs.is_empty()).then(|| serde_json::to_string(&entry.xattrs)).transpose()?,
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok((backup_hash(&entries), stats))
+    }
+
+    pub fn entries(&self, conn: &Connection, journal_file_id: i64) -> Result<Vec<BackupEntry>> {
+        let mut stmt = conn.prepare(
+            "SELECT relative_path, kind, object_hash, size, mode, uid, gid, modified, link_target, xattrs
+             FROM backup_entries WHERE journal_file_id = ?1 ORDER BY id",
+        )?;
+        let entries = stmt
+            .query_map([journal_file_id], |row| {
+                Ok(BackupEntry {
+                    relative_path: row.get::<_, RawPath>(0)?.into(),
+                    kind: parse_kind(&row.get::<_, String>(1)?),
+                    object_hash: row.get(2)?,
+                    size: row.get::<_, i64>(3)? as u64,
+                    mode: row.get(4)?,
+                    uid: row.get(5)?,
+                    gid: row.get(6)?,
+                    modified: row
+                        .get::<_, Option<String>>(7)?
+                        .and_then(|t| DateTime::parse_from_rfc3339(&t).ok())
+                        .map(|t| t.with_timezone(&Utc)),
+                    link_target: row.get::<_, Option<RawPath>>(8)?.map(PathBuf::from),
+                    xattrs: row
+                        .get::<_, Option<String>>(9)?
+                        .and_then(|x| serde_json::from_str(&x).ok())
+                        .unwrap_or_default(),
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(entries)
+    }
+
+    /// Recreates a backup at `destination`. Every object is checked against
+    /// its hash before it is moved into place, so a corrupt backup fails
+    /// instead of restoring damaged data.
+    pub fn restore(&self, conn: &Connection, journal_file_id: i64, destination: &Path) -> Result<()> {
+        let entries = self.entries(conn, journal_file_id)?;
+        if entries.is_empty() {
+            return Err(CleanerError::FileNotFound(format!("backup of journal file {}", journal_file_id)));
+        }
+
+        for entry in &entries {
+            let target = join_relative(destination, &entry.relative_path);
+            match entry.kind {
+                EntryKind::Directory => fs::create_dir_all(&target)?,
+                EntryKind::Symlink => {
+                    let link = entry.link_target.as_deref().unwrap_or(Path::new(""));
+                    #[cfg(unix)]
+                    std::os::unix::fs::symlink(link, &target)?;
+                    #[cfg(windows)]
+                    std::os::windows::fs::symlink_file(link, &target)?;
+                }
+                EntryKind::File => {
+                    let hash = entry
+                        .object_hash
+                        .as_deref()
+                        .ok_or_else(|| CleanerError::Corrupted(format!("{} has no object", target.display())))?;
+                    self.restore_object(conn, hash, &target)?;
+                }
+            }
+        }
+
+        // Metadata last and deepest first, so creating children does not
+        // bump the restored mtime of their directory
+        for entry in entries.iter().rev() {
+            apply_metadata(&join_relative(destination, &entry.relative_path), entry);
+        }
+        Ok(())
+    }
+
+    /// The hash `backup` returned for a journal file, rebuilt from its
+    /// manifest; `None` if no backup was recorded.
+    pub fn manifest_hash(&self, conn: &Connection, journal_file_id: i64) -> Result<Option<String>> {
+        let entries = self.entries(conn, journal_file_id)?;
+        Ok((!entries.is_empty()).then(|| backup_hash(&entries)))
+    }
+
+    /// Checks that every object of a backup is present and matches its hash.
+    pub fn verify(&self, conn: &Connection, journal_file_id: i64) -> Result<()> {
+        for entry in self.entries(conn, journal_file_id)? {
+            if let Some(hash) = entry.object_hash {
+                self.read_object(conn, &hash, &mut io::sink())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops the manifest of a backup. The objects stay until no backup
+    /// refers to them any more.
+    pub fn discard(&self, conn: &Connection, journal_file_id: i64) -> Result<()> {
+        conn.execute("DELETE FROM backup_entries WHERE journal_file_id = ?1", [journal_file_id])?;
+        Ok(())
+    }
+
+    /// Deletes objects that no backup refers to and returns the bytes freed.
+    /// A running operation stores its objects before it records the
+    /// entries that refer to them, so anything stored or reused since the
+    /// oldest pending operation started is left alone.
+    pub fn remove_unreferenced(&self, conn: &Connection) -> Result<u64> {
+        let unreferenced: Vec<(String, i64)> = {
+            let mut stmt = conn.prepare(
+                "SELECT hash, stored_size FROM backup_objects
+                 WHERE hash NOT IN (SELECT object_hash FROM backup_entries WHERE object_hash IS NOT NULL)
+                   AND created_at < COALESCE(
+                       (SELECT MIN(started_at) FROM journal_operations WHERE status = 'pending'),
+                       '9999')",
+            )?;
+            let rows = stmt
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .filter_map(|r| r.ok())
+                .collect();
+            rows
+        };
+
+        let mut freed = 0;
+        for (hash, stored_size) in unreferenced {
+            let object = self.object_path(&hash);
+            match fs::remove_file(&object) {
+                Ok(()) => freed += stored_size as u64,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+            if let Some(prefix_dir) = object.parent() {
+                let _ = fs::remove_dir(prefix_dir);
+            }
+            conn.execute("DELETE FROM backup_objects WHERE hash = ?1", [&hash])?;
+        }
+        Ok(freed)
+    }
+
+    /// Stores a file's contents, hashing and compressing in a single read.
+    /// Returns the hash, the bytes written and whether the object was
+    /// already present.
+    fn put_object(&self, conn: &Connection, path: &Path) -> Result<(String, u64, bool)> {
+        fs::create_dir_all(&self.objects_dir)?;
+        let temp = self.objects_dir.join(format!(
+            ".incoming-{}-{}",
+            std::process::id(),
+            INCOMING.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let mut source = File::open(path)?;
+        let mut hasher = blake3::Hasher::new();
+        let output = File::create(&temp)?;
+        let size = match self.compression_level {
+            Some(level) => {
+                let mut encoder = zstd::Encoder::new(output, level)?;
+                let size = copy_hashing(&mut source, &mut encoder, &mut hasher)?;
+                encoder.finish()?;
+                size
+            }
+            None => copy_hashing(&mut source, &mut &output, &mut hasher)?,
+        };
+        let hash = hasher.finalize().to_hex().to_string();
+        let object = self.object_path(&hash);
+
+        let known: Option<i64> = conn
+            .query_row("SELECT stored_size FROM backup_objects WHERE hash = ?1", [&hash], |row| row.get(0))
+            .optional()?;
+        if known.is_some() && object.exists() {
+            let _ = fs::remove_file(&temp);
+            // Keeps `remove_unreferenced` off it until the entries exist.
+            conn.execute(
+                "UPDATE backup_objects SET created_at = ?2 WHERE hash = ?1",
+                params![hash, Utc::now().to_rfc3339()],
+            )?;
+            return Ok((hash, 0, true));
+        }
+
+        let mut compressed = self.compression_level.is_some();
+        let mut stored_size = fs::metadata(&temp)?.len();
+        // Already-compressed data (media, archives) only grows; keep it raw.
+        // That reads the file a second time, so it must still hash the same.
+        if compressed && stored_size >= size {
+            let mut raw_hasher = blake3::Hasher::new();
+            let copied = copy_hashing(&mut File::open(path)?, &mut File::create(&temp)?, &mut raw_hasher);
+            if copied.is_err() || raw_hasher.finalize().to_hex().as_str() != hash {
+                let _ = fs::remove_file(&temp);
+                copied?;
+                return Err(CleanerError::Changed(path.display().to_string()));
+            }
+            compressed = false;
+            stored_size = size;
+        }
+        File::open(&temp)?.sync_all()?;
+
+        if let Some(parent) = object.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&temp, &object)?;
+        conn.execute(
+            "INSERT OR REPLACE INTO backup_objects (hash, size, stored_size, compressed, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![hash, size as i64, stored_size as i64, compressed, Utc::now().to_rfc3339()],
+        )?;
+        Ok((hash, stored_size, false))
+    }
+
+    /// Writes an object to `target` through a temporary file that is only
+    /// renamed into place once its hash checks out.
+    fn restore_object(&self, conn: &Connection, hash: &str, target: &Path) -> Result<()> {
+        let mut name = std::ffi::OsString::from(".");
+        name.push(target.file_name().unwrap_or_default());
+        name.push(".restoring");
+        let partial = target.with_file_name(name);
+
+        let result = File::create(&partial)
+            .map_err(CleanerError::from)
+            .and_then(|mut file| {
+                self.read_object(conn, hash, &mut file)?;
+                file.sync_all()?;
+                Ok(())
+            });
+        match result {
+            Ok(()) => fs::rename(&partial, target).map_err(Into::into),
+            Err(e) => {
+                let _ = fs::remove_file(&partial);
+                Err(e)
+            }
+        }
+    }
+
+    /// Streams an object's original contents into `output` and fails if they
+    /// do not hash to `hash`.
+    fn read_object(&self, conn: &Connection, hash: &str, output: &mut dyn Write) -> Result<()> {
+        let compressed: bool = conn
+            .query_row("SELECT compressed FROM backup_objects WHERE hash = ?1", [hash], |row| row.get(0))
+            .optional()?
+            .ok_or_else(|| CleanerError::Corrupted(format!("object {} is not in the store", hash)))?;
+        let file = File::open(self.object_path(hash))
+            .map_err(|_| CleanerError::Corrupted(format!("object {} is missing", hash)))?;
+
+        let mut reader: Box<dyn Read> = if compressed {
+            Box::new(zstd::Decoder::new(file).map_err(|e| CleanerError::Corrupted(format!("object {}: {}", hash, e)))?)
+        } else {
+            Box::new(file)
+        };
+        let mut hasher = blake3::Hasher::new();
+        copy_hashing(&mut reader, output, &mut hasher)
+            .map_err(|e| CleanerError::Corrupted(format!("object {}: {}", hash, e)))?;
+
+        if hasher.finalize().to_hex().as_str() != hash {
+            return Err(CleanerError::Corrupted(format!("object {} does not match its hash", hash)));
+        }
+        Ok(())
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.objects_dir.join(&hash[..2]).join(hash)
+    }
+}
+
+fn copy_hashing(reader: &mut dyn Read, writer: &mut dyn Write, hasher: &mut blake3::Hasher) -> io::Result<u64> {
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    let mut total = 0u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(total),
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+}
+
+/// A single file is identified by its content hash; a tree by a hash over
+/// its layout and the hashes of its files.
+fn backup_hash(entries: &[BackupEntry]) -> String {
+    if let [entry] = entries {
+        if let Some(ref hash) = entry.object_hash {
+            return hash.clone();
+        }
+    }
+    let mut hasher = blake3::Hasher::new();
+    for entry in entries {
+        hasher.update(&path_to_bytes(&entry.relative_path));
+        hasher.update(&[0]);
+        hasher.update(kind_str(entry.kind).as_bytes());
+        hasher.update(&[0]);
+        hasher.update(entry.object_hash.as_deref().unwrap_or("").as_bytes());
+        hasher.update(&entry.link_target.as_deref().map(path_to_bytes).unwrap_or_default());
+        hasher.update(&[b'\n']);
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+fn join_relative(root: &Path, relative: &Path) -> PathBuf {
+    if relative.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(relative)
+    }
+}
+
+/// Best effort: ownership needs privileges and xattrs need filesystem
+/// support, neither of which should fail the restore.
+fn apply_metadata(path: &Path, entry: &BackupEntry) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if entry.uid.is_some() || entry.gid.is_some() {
+            let _ = std::os::unix::fs::lchown(path, entry.uid, entry.gid);
+        }
+        for (name, value) in &entry.xattrs {
+            if let Err(e) = xattr::set(path, name, value) {
+                tracing::debug!("Could not restore xattr {} on {:?}: {}", name, path, e);
+            }
+        }
+        if entry.kind != EntryKind::Symlink {
+            let _ = fs::set_permissions(path, fs::Permissions::from_mode(entry.mode));
+        }
+    }
+    #[cfg(windows)]
+    {
+        if let Ok(metadata) = fs::metadata(path) {
+            let mut permissions = metadata.permissions();
+            permissions.set_readonly(entry.mode & 0o200 == 0);
+            let _ = fs::set_permissions(path, permissions);
+        }
+    }
+
+    if let (Some(modified), true) = (entry.modified, entry.kind != EntryKind::Symlink) {
+        if let Ok(file) = File::open(path) {
+            let _ = file.set_modified(modified.into());
+        }
+    }
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn file_mode(metadata: &fs::Metadata) -> u32 {
+    if metadata.permissions().readonly() { 0o444 } else { 0o644 }
+}
+
+#[cfg(unix)]
+fn owner(metadata: &fs::Metadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.uid(), metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn owner(_metadata: &fs::Metadata) -> Option<(u32, u32)> {
+    None
+}
+
+#[cfg(unix)]
+fn read_xattrs(path: &Path) -> Vec<(String, Vec<u8>)> {
+    let Ok(names) = xattr::list(path) else { return Vec::new() };
+    names
+        .filter_map(|name| {
+            let value = xattr::get(path, &name).ok()??;
+            Some((name.to_string_lossy().to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn read_xattrs(_path: &Path) -> Vec<(String, Vec<u8>)> {
+    Vec::new()
+}
+
+fn kind_str(kind: EntryKind) -> &'static str {
+    match kind {
+        EntryKind::File => "file",
+        EntryKind::Directory => "directory",
+        EntryKind::Symlink => "symlink",
+    }
+}
+
+fn parse_kind(value: &str) -> EntryKind {
+    match value {
+        "directory" => EntryKind::Directory,
+        "symlink" => EntryKind::Symlink,
+        _ => EntryKind::File,
+    }
+}
@@ -0,0 +1,185 @@
+// src-tauri/src/cleanup/confirmation.rs
+use crate::error::{CleanerError, Result};
+use crate::scanner::{dir_usage, CleanupRule, LocalRules, RiskLevel, RuleEngine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a prepared operation can be confirmed.
+pub const CONFIRMATION_TTL: Duration = Duration::from_secs(120);
+
+/// Commands that cannot be undone and so only run with a token from a
+/// matching prepare call.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum IrreversibleAction {
+    /// `delete_files` without the trash.
+    Delete,
+    SecureDelete,
+    RemoveStartupItem,
+    PurgeTrash,
+    /// `purge_quarantine` with `all`, before the grace period is over.
+    PurgeQuarantine,
+    WipeFreeSpace,
+    /// `run_backup_gc` outside a dry run.
+    BackupGc,
+}
+
+/// What the user is asked to confirm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmationSummary {
+    /// Passed back with the operation; valid once, for exactly these targets.
+    pub token: String,
+    pub action: IrreversibleAction,
+    pub count: u64,
+    pub bytes: u64,
+    pub highest_risk: RiskLevel,
+    /// `RiskLevel::High` operations must also be run with
+    /// `acknowledge_high_risk` set, after the user was shown why.
+    pub requires_acknowledgement: bool,
+    pub expires_at: DateTime<Utc>,
+}
+
+struct Pending {
+    action: IrreversibleAction,
+    targets: BTreeSet<PathBuf>,
+    highest_risk: RiskLevel,
+    expires: Instant,
+}
+
+/// Tokens issued by prepare calls, kept in memory only: a restart
+/// invalidates them all.
+#[derive(Default)]
+pub struct ConfirmationStore {
+    pending: Mutex<HashMap<String, Pending>>,
+}
+
+impl ConfirmationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Issues a token for running `action` on `targets`.
+    pub fn issue(
+        &self,
+        action: IrreversibleAction,
+        targets: &[PathBuf],
+        bytes: u64,
+        highest_risk: RiskLevel,
+    ) -> ConfirmationSummary {
+        let token: String = rand::random::<[u8; 16]>().iter().map(|b| format!("{:02x}", b)).collect();
+        let targets: BTreeSet<PathBuf> = targets.iter().cloned().collect();
+        let summary = ConfirmationSummary {
+            token: token.clone(),
+            action,
+            count: targets.len() as u64,
+            bytes,
+            requires_acknowledgement: highest_risk == RiskLevel::High,
+            highest_risk: highest_risk.clone(),
+            expires_at: Utc::now() + chrono::Duration::from_std(CONFIRMATION_TTL).unwrap_or_default(),
+        };
+
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        pending.retain(|_, p| p.expires > now);
+        pending.insert(
+            token,
+            Pending {
+                action,
+                targets,
+                highest_risk,
+                expires: now + CONFIRMATION_TTL,
+            },
+        );
+        summary
+    }
+
+    /// Checks and uses up `token`. It must have been issued for `action`
+    /// on exactly `targets`, not have expired, and a high-risk operation
+    /// must be `acknowledged`. A token is gone after one attempt, whether
+    /// or not it checked out.
+    pub fn redeem(
+        &self,
+        token: Option<&str>,
+        action: IrreversibleAction,
+        targets: &[PathBuf],
+        acknowledged: bool,
+    ) -> Result<()> {
+        let token = token.ok_or_else(|| {
+            CleanerError::ConfirmationRequired(format!("{:?} needs a token from its prepare call", action))
+        })?;
+        let pending = self
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(token)
+            .ok_or_else(|| CleanerError::ConfirmationRequired("unknown or already used token".to_string()))?;
+
+        if pending.expires <= Instant::now() {
+            return Err(CleanerError::ConfirmationRequired("the token has expired".to_string()));
+        }
+        if pending.action != action {
+            return Err(CleanerError::ConfirmationRequired(format!(
+                "the token was issued for {:?}, not {:?}",
+                pending.action, action
+            )));
+        }
+        if pending.targets != targets.iter().cloned().collect::<BTreeSet<_>>() {
+            return Err(CleanerError::ConfirmationRequired(
+                "the paths differ from the ones that were confirmed".to_string(),
+            ));
+        }
+        if pending.highest_risk == RiskLevel::High && !acknowledged {
+            return Err(CleanerError::ConfirmationRequired(
+                "high-risk operations must be acknowledged".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Size and highest risk of removing `paths`, for the prepare call.
+pub fn assess_paths(paths: &[PathBuf]) -> (u64, RiskLevel) {
+    let rules = RuleEngine::new(CleanupRule::default_rules());
+    let local_rules = LocalRules::new();
+    let mut bytes = 0;
+    let mut highest = RiskLevel::Safe;
+
+    for path in paths {
+        let Ok(metadata) = fs::symlink_metadata(path) else { continue };
+        bytes += if metadata.is_dir() { dir_usage(path).size } else { metadata.len() };
+        highest = highest.max(path_risk(path, &metadata, &rules, &local_rules));
+    }
+    (bytes, highest)
+}
+
+/// Protected or `.sweepignore`d paths are high risk. Otherwise the
+/// matching rule decides, and a path no rule recognises is medium risk
+/// inside the home and temp directories and high risk anywhere else,
+/// including those directories themselves. A `.systemsweep.toml` risk
+/// level can only raise that, never lower it.
+fn path_risk(path: &Path, metadata: &fs::Metadata, rules: &RuleEngine, local_rules: &LocalRules) -> RiskLevel {
+    if local_rules.is_ignored(path, metadata.is_dir()) {
+        return RiskLevel::High;
+    }
+    let risk = match rules.match_rule(path, metadata) {
+        Some(rule) => rule.risk_level.clone(),
+        None => location_risk(path),
+    };
+    match local_rules.overrides_for(path).risk_level {
+        Some(declared) => risk.max(declared),
+        None => risk,
+    }
+}
+
+fn location_risk(path: &Path) -> RiskLevel {
+    let user_dirs = [dirs::home_dir(), Some(std::env::temp_dir()), Some(PathBuf::from("/var/tmp"))];
+    if user_dirs.iter().flatten().any(|dir| path.starts_with(dir) && path != dir) {
+        RiskLevel::Medium
+    } else {
+        RiskLevel::High
+    }
+}
@@ -0,0 +1,200 @@
+// src-tauri/src/cleanup/executor.rs
+use super::journal::{Journal, JournalFile};
+use super::secure_delete::SecureDeleteOptions;
+use crate::error::{CleanerError, Result};
+use crate::scanner::RuleEngine;
+use crate::state::{AppState, ScanProgress};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long a worker waits for another worker's journal write.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Progress of one cleanup, shared by its workers and reported through
+/// `AppState` like scan progress. Cancelled through `cancel_scan`.
+pub struct CleanupProgress {
+    state: Option<Arc<AppState>>,
+    total_files: u64,
+    total_bytes: u64,
+    files_done: AtomicU64,
+    bytes_done: AtomicU64,
+    entries_removed: AtomicU64,
+}
+
+impl CleanupProgress {
+    pub fn new(state: Option<Arc<AppState>>, total_files: u64, total_bytes: u64) -> Self {
+        Self {
+            state,
+            total_files,
+            total_bytes,
+            files_done: AtomicU64::new(0),
+            bytes_done: AtomicU64::new(0),
+            entries_removed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.as_ref().map(|s| s.is_cancelled()).unwrap_or(false)
+    }
+
+    /// Called between batches while a large tree is removed.
+    pub fn checkpoint(&self, path: &Path, entries_removed: u64) -> Result<()> {
+        self.entries_removed.fetch_add(entries_removed, Ordering::Relaxed);
+        self.report(path);
+        if self.is_cancelled() {
+            return Err(CleanerError::Cancelled);
+        }
+        Ok(())
+    }
+
+    fn file_done(&self, file: &JournalFile) {
+        self.files_done.fetch_add(1, Ordering::Relaxed);
+        self.bytes_done.fetch_add(file.size, Ordering::Relaxed);
+        self.entries_removed.fetch_add(1, Ordering::Relaxed);
+        self.report(&file.original_path);
+    }
+
+    fn report(&self, path: &Path) {
+        let Some(ref s) = self.state else { return };
+        let bytes_done = self.bytes_done.load(Ordering::Relaxed);
+        let percentage = if self.total_bytes > 0 {
+            bytes_done as f32 / self.total_bytes as f32 * 100.0
+        } else {
+            self.files_done.load(Ordering::Relaxed) as f32 / self.total_files.max(1) as f32 * 100.0
+        };
+        s.update_progress(ScanProgress {
+            current_path: path.to_string_lossy().to_string(),
+            files_scanned: self.entries_removed.load(Ordering::Relaxed),
+            bytes_scanned: bytes_done,
+            files_found: self.total_files,
+            bytes_found: self.total_bytes,
+            phase: "cleaning".to_string(),
+            percentage,
+        });
+    }
+}
+
+/// Applies the files of a journal operation on a pool of blocking worker
+/// threads, at most `workers_per_device` per filesystem so one slow disk
+/// does not hold up the others and no disk is flooded with requests. Each
+/// worker writes the journal through its own connection.
+pub struct CleanupExecutor {
+    app_data_dir: PathBuf,
+    secure_delete: SecureDeleteOptions,
+    workers_per_device: usize,
+    rules: Option<Arc<RuleEngine>>,
+}
+
+impl CleanupExecutor {
+    pub fn new(app_data_dir: &Path, secure_delete: SecureDeleteOptions, workers_per_device: usize) -> Self {
+        Self {
+            app_data_dir: app_data_dir.to_path_buf(),
+            secure_delete,
+            workers_per_device: workers_per_device.max(1),
+            rules: None,
+        }
+    }
+
+    /// Rules the workers' journals name in the audit log.
+    pub fn with_rules(mut self, rules: Arc<RuleEngine>) -> Self {
+        self.rules = Some(rules);
+        self
+    }
+
+    /// Executes every file and returns them with their outcome, in journal
+    /// order. Files the cancellation kept from starting have no outcome.
+    pub fn run(
+        &self,
+        db_conn: &Connection,
+        files: Vec<JournalFile>,
+        progress: &Arc<CleanupProgress>,
+    ) -> Result<Vec<(JournalFile, Option<Result<()>>)>> {
+        // An in-memory database cannot be shared between connections
+        let Some(db_path) = db_conn.path().filter(|p| !p.is_empty()).map(PathBuf::from) else {
+            let journal = self.journal(db_conn, progress);
+            return Ok(files
+                .into_iter()
+                .map(|file| {
+                    let outcome = step(&journal, &file, progress);
+                    (file, outcome)
+                })
+                .collect());
+        };
+
+        let mut by_device: HashMap<u64, Vec<JournalFile>> = HashMap::new();
+        for file in files {
+            by_device.entry(device_of(&file.original_path)).or_default().push(file);
+        }
+
+        let mut queues = Vec::new();
+        let mut workers = Vec::new();
+        for group in by_device.into_values() {
+            for _ in 0..self.workers_per_device.min(group.len()) {
+                workers.push((queues.len(), open_worker(&db_path)?));
+            }
+            queues.push(Mutex::new(group.into_iter()));
+        }
+
+        let outcomes = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for (queue, conn) in workers {
+                let (queue, outcomes) = (&queues[queue], &outcomes);
+                scope.spawn(move || {
+                    let journal = self.journal(&conn, progress);
+                    loop {
+                        let next = queue.lock().unwrap_or_else(|e| e.into_inner()).next();
+                        let Some(file) = next else { break };
+                        let outcome = step(&journal, &file, progress);
+                        outcomes.lock().unwrap_or_else(|e| e.into_inner()).push((file, outcome));
+                    }
+                });
+            }
+        });
+
+        let mut outcomes = outcomes.into_inner().unwrap_or_else(|e| e.into_inner());
+        outcomes.sort_by_key(|(file, _)| file.id);
+        Ok(outcomes)
+    }
+
+    fn journal<'c>(&self, conn: &'c Connection, progress: &Arc<CleanupProgress>) -> Journal<'c> {
+        let journal = Journal::new(conn, &self.app_data_dir)
+            .with_secure_delete(self.secure_delete.clone())
+            .with_progress(progress.clone());
+        match self.rules {
+            Some(ref rules) => journal.with_rules(rules.clone()),
+            None => journal,
+        }
+    }
+}
+
+fn step(journal: &Journal, file: &JournalFile, progress: &CleanupProgress) -> Option<Result<()>> {
+    if progress.is_cancelled() {
+        return None;
+    }
+    let outcome = journal.execute(file);
+    progress.file_done(file);
+    Some(outcome)
+}
+
+fn open_worker(db_path: &Path) -> Result<Connection> {
+    let conn = Connection::open(db_path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    Ok(conn)
+}
+
+fn device_of(path: &Path) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        std::fs::symlink_metadata(path).map(|m| m.dev()).unwrap_or(0)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        0
+    }
+}
@@ -0,0 +1,241 @@
+// src-tauri/src/cleanup/free_space.rs
+use super::secure_delete::device_limitations;
+use super::trash_bin::trash_directories;
+use crate::error::{CleanerError, Result};
+use crate::paths::serde_path;
+use crate::system::mounts::available_space;
+use crate::state::{AppState, ScanProgress};
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+/// Fill files are split so FAT32 (4 GiB file limit) mounts work too.
+const FILL_FILE_SIZE: u64 = 1024 * 1024 * 1024;
+/// How often free space is re-read while filling, so space other programs
+/// take in the meantime still counts against the safety margin.
+const RECHECK_INTERVAL: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FreeSpaceWipeOptions {
+    /// Space left free for the rest of the system while the wipe runs.
+    pub safety_margin_bytes: u64,
+    /// Random data instead of zeros. Zeros are faster, but filesystems that
+    /// compress (btrfs, ZFS) store them in almost no blocks at all.
+    pub random_data: bool,
+}
+
+impl Default for FreeSpaceWipeOptions {
+    fn default() -> Self {
+        Self {
+            safety_margin_bytes: 512 * 1024 * 1024,
+            random_data: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreeSpaceWipeResult {
+    #[serde(with = "serde_path")]
+    pub mount_point: PathBuf,
+    pub bytes_written: u64,
+    pub files_created: u64,
+    /// Free space when the wipe started, as available to unprivileged
+    /// users; the root-reserved blocks are never touched.
+    pub available_before: u64,
+    pub warnings: Vec<String>,
+}
+
+/// Overwrites the free space of a mount by filling it with generated files,
+/// syncing them and removing them again. The fill files are always removed,
+/// also when the wipe fails or is cancelled.
+pub struct FreeSpaceWiper {
+    options: FreeSpaceWipeOptions,
+}
+
+impl FreeSpaceWiper {
+    pub fn new(options: FreeSpaceWipeOptions) -> Self {
+        Self { options }
+    }
+
+    pub fn wipe(&self, mount_point: &Path, state: Option<Arc<AppState>>) -> Result<FreeSpaceWipeResult> {
+        let available_before = available_space(mount_point)?;
+        if available_before <= self.options.safety_margin_bytes {
+            return Err(CleanerError::System(format!(
+                "{} has less free space than the safety margin",
+                mount_point.display()
+            )));
+        }
+
+        let warnings: Vec<String> = device_limitations(mount_point).iter().map(|l| l.to_string()).collect();
+        for warning in &warnings {
+            tracing::warn!("Free-space wipe: {}", warning);
+        }
+
+        let fill_dir = create_fill_dir(mount_point)?;
+
+        let mut fill = Fill {
+            dir: &fill_dir,
+            options: &self.options,
+            target: available_before - self.options.safety_margin_bytes,
+            bytes_written: 0,
+            files_created: 0,
+            state,
+        };
+        let filled = fill.run();
+        let (bytes_written, files_created) = (fill.bytes_written, fill.files_created);
+
+        if let Some(ref s) = fill.state {
+            s.update_progress(progress(mount_point, bytes_written, fill.target, "removing_fill_files", 100.0));
+        }
+        let removed = fs::remove_dir_all(&fill_dir);
+        filled?;
+        removed?;
+
+        Ok(FreeSpaceWipeResult {
+            mount_point: mount_point.to_path_buf(),
+            bytes_written,
+            files_created,
+            available_before,
+            warnings,
+        })
+    }
+}
+
+/// Creates the directory for the fill files in the first writable place on
+/// the mount: its root, the home directory, one of the user's trash
+/// directories or the temp directory, whichever lives on that filesystem.
+/// The roots of `/` and `/home` belong to root.
+fn create_fill_dir(mount_point: &Path) -> Result<PathBuf> {
+    let name = format!(".systemsweep-wipe-{}", std::process::id());
+    let mut candidates = vec![mount_point.to_path_buf()];
+    candidates.extend(dirs::home_dir());
+    candidates.extend(trash_directories().into_iter().map(|(path, _)| path));
+    candidates.push(std::env::temp_dir());
+
+    for dir in candidates.iter().filter(|dir| same_device(dir, mount_point)) {
+        match fs::create_dir(dir.join(&name)) {
+            Ok(()) => return Ok(dir.join(&name)),
+            Err(e) if matches!(e.kind(), io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem) => {
+                continue
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(CleanerError::PermissionDenied(format!(
+        "no directory on {} is writable for the current user",
+        mount_point.display()
+    )))
+}
+
+fn same_device(dir: &Path, mount_point: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        match (fs::metadata(dir), fs::metadata(mount_point)) {
+            (Ok(a), Ok(b)) => a.dev() == b.dev(),
+            _ => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        dir == mount_point
+    }
+}
+
+struct Fill<'a> {
+    dir: &'a Path,
+    options: &'a FreeSpaceWipeOptions,
+    target: u64,
+    bytes_written: u64,
+    files_created: u64,
+    state: Option<Arc<AppState>>,
+}
+
+impl Fill<'_> {
+    fn run(&mut self) -> Result<()> {
+        let mut rng = StdRng::from_entropy();
+        let mut buffer = vec![0u8; BUFFER_SIZE];
+        let mut file: Option<File> = None;
+        let mut file_written = 0u64;
+        let mut since_check = 0u64;
+
+        while self.bytes_written < self.target {
+            if let Some(ref s) = self.state {
+                if s.is_cancelled() {
+                    return Err(CleanerError::Cancelled);
+                }
+            }
+
+            if since_check >= RECHECK_INTERVAL {
+                since_check = 0;
+                // Keep the margin even if something else filled the disk
+                if available_space(self.dir)? <= self.options.safety_margin_bytes {
+                    break;
+                }
+                if let Some(ref s) = self.state {
+                    let percentage = self.bytes_written as f32 / self.target as f32 * 100.0;
+                    s.update_progress(progress(self.dir, self.bytes_written, self.target, "wipe_free_space", percentage));
+                }
+            }
+
+            if file.is_none() || file_written >= FILL_FILE_SIZE {
+                if let Some(done) = file.take() {
+                    done.sync_all()?;
+                }
+                let path = self.dir.join(format!("fill-{:05}", self.files_created));
+                file = Some(OpenOptions::new().write(true).create_new(true).open(path)?);
+                self.files_created += 1;
+                file_written = 0;
+            }
+
+            let chunk = (self.target - self.bytes_written).min(BUFFER_SIZE as u64) as usize;
+            if self.options.random_data {
+                rng.fill_bytes(&mut buffer[..chunk]);
+            }
+            match file.as_mut().map(|f| f.write_all(&buffer[..chunk])) {
+                Some(Err(e)) if is_out_of_space(&e) => break,
+                Some(Err(e)) => return Err(e.into()),
+                _ => {}
+            }
+            self.bytes_written += chunk as u64;
+            file_written += chunk as u64;
+            since_check += chunk as u64;
+        }
+
+        // The data has to reach the disk before the files are removed
+        match file.map(|f| f.sync_all()) {
+            Some(Err(e)) if !is_out_of_space(&e) => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn progress(path: &Path, written: u64, target: u64, phase: &str, percentage: f32) -> ScanProgress {
+    ScanProgress {
+        current_path: path.to_string_lossy().to_string(),
+        files_scanned: 0,
+        bytes_scanned: written,
+        files_found: 0,
+        bytes_found: target,
+        phase: phase.to_string(),
+        percentage,
+    }
+}
+
+fn is_out_of_space(error: &io::Error) -> bool {
+    #[cfg(unix)]
+    {
+        error.raw_os_error() == Some(libc::ENOSPC)
+    }
+    #[cfg(not(unix))]
+    {
+        error.kind() == io::ErrorKind::StorageFull
+    }
+}
@@ -0,0 +1,259 @@
+// src-tauri/src/cleanup/history.rs
+use crate::error::Result;
+use crate::paths::{serde_path, RawPath, SqlPath};
+use crate::scanner::{FileCategory, RuleEngine};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One file (or directory) a cleanup removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanedFile {
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    pub category: FileCategory,
+    pub rule_id: Option<String>,
+    pub bytes: u64,
+}
+
+impl CleanedFile {
+    /// Categorizes `path` by the rule that matches it. Call before the file
+    /// is removed, while its metadata can still be read.
+    pub fn classify(rules: &RuleEngine, path: &Path, bytes: u64) -> Self {
+        let rule = fs::symlink_metadata(path).ok().and_then(|m| rules.match_rule(path, &m).cloned());
+        Self {
+            path: path.to_path_buf(),
+            category: rule.as_ref().map(|r| r.category.clone()).unwrap_or(FileCategory::Unknown),
+            rule_id: rule.map(|r| r.id),
+            bytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ReclaimedGrouping {
+    Week,
+    Month,
+    Category,
+    Rule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReclaimedSpace {
+    /// `2024-W05`, `2024-02`, a category name or a rule id.
+    pub key: String,
+    pub bytes: u64,
+    pub files: u64,
+    pub cleanups: u64,
+}
+
+/// A directory that junk keeps reappearing in.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JunkSource {
+    #[serde(with = "serde_path")]
+    pub source: PathBuf,
+    pub cleanups: u64,
+    pub files: u64,
+    pub bytes: u64,
+    pub last_cleaned: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryFileRow {
+    pub history_id: i64,
+    pub timestamp: String,
+    pub operation_type: String,
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    pub category: String,
+    pub rule_id: Option<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+/// Records a finished cleanup and its files; returns the history id.
+pub fn add_cleanup_history(
+    conn: &Connection,
+    operation_type: &str,
+    operation_id: Option<i64>,
+    files: &[CleanedFile],
+) -> Result<i64> {
+    let tx = conn.unchecked_transaction()?;
+    let bytes: u64 = files.iter().map(|f| f.bytes).sum();
+    tx.execute(
+        "INSERT INTO cleanup_history (timestamp, operation_type, files_count, bytes_cleaned, operation_id)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![Utc::now().to_rfc3339(), operation_type, files.len() as i64, bytes as i64, operation_id],
+    )?;
+    let history_id = tx.last_insert_rowid();
+
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO cleanup_history_files (history_id, path, source, category, rule_id, bytes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for file in files {
+            let source = file.path.parent().unwrap_or(Path::new(""));
+            stmt.execute(params![
+                history_id,
+                SqlPath(&file.path),
+                SqlPath(source),
+                format!("{:?}", file.category),
+                file.rule_id,
+                file.bytes as i64,
+            ])?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(history_id)
+}
+
+pub fn cleanup_history_files(conn: &Connection, history_id: i64) -> Result<Vec<HistoryFileRow>> {
+    query_files(conn, "WHERE h.id = ?1", params![history_id])
+}
+
+/// Bytes reclaimed since `since` (all time when `None`), newest period or
+/// largest group first.
+pub fn reclaimed_space(
+    conn: &Connection,
+    grouping: ReclaimedGrouping,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<ReclaimedSpace>> {
+    let (key, order) = match grouping {
+        // %W numbers weeks from the first Monday of the year
+        ReclaimedGrouping::Week => ("strftime('%Y-W%W', h.timestamp)", "key DESC"),
+        ReclaimedGrouping::Month => ("strftime('%Y-%m', h.timestamp)", "key DESC"),
+        ReclaimedGrouping::Category => ("f.category", "bytes DESC"),
+        ReclaimedGrouping::Rule => ("COALESCE(f.rule_id, 'none')", "bytes DESC"),
+    };
+    let sql = format!(
+        "SELECT {key} AS key, SUM(f.bytes) AS bytes, COUNT(*), COUNT(DISTINCT h.id)
+         FROM cleanup_history_files f JOIN cleanup_history h ON h.id = f.history_id
+         WHERE h.timestamp >= ?1
+         GROUP BY key ORDER BY {order}",
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt
+        .query_map([since_str(since)], |row| {
+            Ok(ReclaimedSpace {
+                key: row.get(0)?,
+                bytes: row.get::<_, i64>(1)? as u64,
+                files: row.get::<_, i64>(2)? as u64,
+                cleanups: row.get::<_, i64>(3)? as u64,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// Directories that had files removed in more than one cleanup, most
+/// recurring first.
+pub fn recurring_junk_sources(conn: &Connection, limit: u32) -> Result<Vec<JunkSource>> {
+    let mut stmt = conn.prepare(
+        "SELECT f.source, COUNT(DISTINCT h.id) AS cleanups, COUNT(*), SUM(f.bytes) AS bytes, MAX(h.timestamp)
+         FROM cleanup_history_files f JOIN cleanup_history h ON h.id = f.history_id
+         GROUP BY f.source HAVING cleanups > 1
+         ORDER BY cleanups DESC, bytes DESC LIMIT ?1",
+    )?;
+    let sources = stmt
+        .query_map([limit], |row| {
+            Ok(JunkSource {
+                source: row.get::<_, RawPath>(0)?.into(),
+                cleanups: row.get::<_, i64>(1)? as u64,
+                files: row.get::<_, i64>(2)? as u64,
+                bytes: row.get::<_, i64>(3)? as u64,
+                last_cleaned: row.get(4)?,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(sources)
+}
+
+/// Writes one row per cleaned file to `out` and returns the row count.
+pub fn export_cleanup_history(
+    conn: &Connection,
+    format: HistoryExportFormat,
+    since: Option<DateTime<Utc>>,
+    out: &mut dyn Write,
+) -> Result<u64> {
+    let rows = query_files(conn, "WHERE h.timestamp >= ?1", params![since_str(since)])?;
+
+    match format {
+        HistoryExportFormat::Json => serde_json::to_writer_pretty(&mut *out, &rows)?,
+        HistoryExportFormat::Csv => {
+            writeln!(out, "history_id,timestamp,operation_type,path,category,rule_id,bytes")?;
+            for row in &rows {
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{}",
+                    row.history_id,
+                    csv_field(&row.timestamp),
+                    csv_field(&row.operation_type),
+                    // Spreadsheets want text; the JSON export keeps raw bytes
+                    csv_field(&row.path.to_string_lossy()),
+                    csv_field(&row.category),
+                    csv_field(row.rule_id.as_deref().unwrap_or("")),
+                    row.bytes
+                )?;
+            }
+        }
+    }
+    Ok(rows.len() as u64)
+}
+
+pub fn clear_cleanup_history(conn: &Connection) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM cleanup_history_files", [])?;
+    tx.execute("DELETE FROM cleanup_history", [])?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn query_files(conn: &Connection, filter: &str, params: impl rusqlite::Params) -> Result<Vec<HistoryFileRow>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT h.id, h.timestamp, h.operation_type, f.path, f.category, f.rule_id, f.bytes
+         FROM cleanup_history_files f JOIN cleanup_history h ON h.id = f.history_id
+         {} ORDER BY h.timestamp, f.id",
+        filter
+    ))?;
+    let rows = stmt
+        .query_map(params, |row| {
+            Ok(HistoryFileRow {
+                history_id: row.get(0)?,
+                timestamp: row.get(1)?,
+                operation_type: row.get(2)?,
+                path: row.get::<_, RawPath>(3)?.into(),
+                category: row.get(4)?,
+                rule_id: row.get(5)?,
+                bytes: row.get::<_, i64>(6)? as u64,
+            })
+        })?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(rows)
+}
+
+/// RFC 3339 timestamps compare correctly as text.
+fn since_str(since: Option<DateTime<Utc>>) -> String {
+    since.map(|t| t.to_rfc3339()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,813 @@
+// src-tauri/src/cleanup/journal.rs
+use super::audit::{AuditAction, AuditEvent, AuditLog, AuditOutcome};
+use super::backup_store::BackupStore;
+use super::executor::CleanupProgress;
+use super::quarantine::Quarantine;
+use super::secure_delete::{secure_delete, SecureDeleteOptions};
+use super::restore::{ConflictPolicy, RestoreOutcome, RestoreReport, RestoredFile};
+use super::trash_bin::{free_restore_path, restore_from_trash_to};
+use super::unlink::remove_tree;
+use crate::error::{CleanerError, Result};
+use crate::paths::{serde_path, RawPath, SqlPath};
+use crate::scanner::{dir_usage, CleanupRule, FileIdentity, RuleEngine};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// What a journal entry does to its file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum JournalAction {
+    Trash,
+    Delete,
+    SecureDelete,
+    /// Renamed into the quarantine directory; purged after a grace period.
+    Quarantine,
+    /// The file is changed in place (e.g. a history database); only its backup is journaled.
+    Modify,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum OperationStatus {
+    /// Written before any file is touched; still pending after a crash.
+    Pending,
+    Committed,
+    /// Found pending on startup; waits for `recover` to resume or roll it back.
+    Interrupted,
+    RolledBack,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum FileStatus {
+    Pending,
+    Done,
+    Skipped,
+    Failed,
+    Restored,
+    /// The backup was dropped by retention; the file can no longer be restored.
+    Expired,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RecoveryPolicy {
+    /// Finish the files that were not processed yet.
+    Resume,
+    /// Put back everything the operation already removed.
+    Rollback,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalOperation {
+    pub id: i64,
+    pub kind: String,
+    /// Caller-supplied identifier, e.g. the UUID of an older undo record.
+    pub reference: Option<String>,
+    pub status: OperationStatus,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub files_count: u64,
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalFile {
+    pub id: i64,
+    pub operation_id: i64,
+    #[serde(with = "serde_path")]
+    pub original_path: PathBuf,
+    pub action: JournalAction,
+    /// A backup is taken before the action is applied.
+    pub backup: bool,
+    /// Identifies the stored backup once it has been taken.
+    pub backup_hash: Option<String>,
+    /// Where a quarantined file was moved to.
+    #[serde(with = "serde_path::option")]
+    pub quarantine_path: Option<PathBuf>,
+    /// Device and inode when the operation began; the action is refused if
+    /// the path refers to another file by the time it runs.
+    pub identity: Option<FileIdentity>,
+    pub size: u64,
+    pub status: FileStatus,
+    pub processed_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryResult {
+    pub operation_id: i64,
+    pub status: OperationStatus,
+    pub files_processed: u64,
+    #[serde(with = "serde_path::failures")]
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// The single write-ahead record of every deletion. Each operation and all
+/// of its files are written as pending before anything is touched, so a
+/// crash leaves enough behind to finish or undo the work.
+pub struct Journal<'a> {
+    conn: &'a Connection,
+    store: BackupStore,
+    quarantine: Quarantine,
+    audit: AuditLog,
+    secure_delete: SecureDeleteOptions,
+    progress: Option<Arc<CleanupProgress>>,
+    /// Names the rule of each audited file; built on first use unless the
+    /// caller shares its own.
+    rules: OnceLock<Arc<RuleEngine>>,
+}
+
+impl<'a> Journal<'a> {
+    pub fn new(conn: &'a Connection, app_data_dir: &Path) -> Self {
+        Self {
+            conn,
+            store: BackupStore::new(app_data_dir),
+            quarantine: Quarantine::new(app_data_dir),
+            audit: AuditLog::new(app_data_dir),
+            secure_delete: SecureDeleteOptions::default(),
+            progress: None,
+            rules: OnceLock::new(),
+        }
+    }
+
+    /// Shares a rule engine, so a cleanup builds it once instead of once per
+    /// worker.
+    pub fn with_rules(self, rules: Arc<RuleEngine>) -> Self {
+        let _ = self.rules.set(rules);
+        self
+    }
+
+    fn rules(&self) -> &RuleEngine {
+        self.rules.get_or_init(|| Arc::new(RuleEngine::new(CleanupRule::default_rules())))
+    }
+
+    /// How `JournalAction::SecureDelete` files are wiped.
+    pub fn with_secure_delete(mut self, options: SecureDeleteOptions) -> Self {
+        self.secure_delete = options;
+        self
+    }
+
+    /// Reports progress while large directories are removed and lets the
+    /// removal be cancelled between batches.
+    pub fn with_progress(mut self, progress: Arc<CleanupProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Records a pending operation with one pending row per file. Files with
+    /// `with_backup` are copied to the backup store before they are touched.
+    pub fn begin(&self, kind: &str, reference: Option<&str>, files: &[(PathBuf, JournalAction, bool)]) -> Result<i64> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO journal_operations (kind, reference, status, started_at) VALUES (?1, ?2, ?3, ?4)",
+            params![kind, reference, status_str(OperationStatus::Pending), Utc::now().to_rfc3339()],
+        )?;
+        let operation_id = tx.last_insert_rowid();
+
+        for (path, action, with_backup) in files {
+            let metadata = fs::symlink_metadata(path).ok();
+            let size = match metadata {
+                Some(ref m) if m.is_dir() => dir_usage(path).size,
+                Some(ref m) => m.len(),
+                None => 0,
+            };
+            let identity = metadata.as_ref().and_then(FileIdentity::of);
+            tx.execute(
+                "INSERT INTO journal_files (operation_id, original_path, action, backup, file_size, status, device, inode)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    operation_id,
+                    SqlPath(path),
+                    action_str(*action),
+                    with_backup,
+                    size as i64,
+                    file_status_str(FileStatus::Pending),
+                    identity.map(|i| i.device as i64),
+                    identity.map(|i| i.inode as i64),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(operation_id)
+    }
+
+    /// Backs the file up (if planned) and then applies its action.
+    pub fn execute(&self, file: &JournalFile) -> Result<()> {
+        let path = &file.original_path;
+        let action = audit_action(file.action);
+        // The backup hashes the contents on its way, so they are read once
+        let mut event = if file.backup { AuditEvent::unhashed(action, path) } else { AuditEvent::new(action, path) }
+            .rule(self.rules(), path)
+            .operation(file.operation_id);
+        let result = self.apply(file).map(|backup_hash| {
+            if event.size.is_some() {
+                event.content_hash = backup_hash;
+            }
+        });
+        match &result {
+            Ok(()) => {
+                self.mark_file(file.id, FileStatus::Done, None)?;
+                self.audit.record(event, AuditOutcome::Succeeded, None)?;
+            }
+            Err(e) => {
+                self.mark_file(file.id, FileStatus::Failed, Some(&e.to_string()))?;
+                self.audit.record(event, AuditOutcome::Failed, Some(&e.to_string()))?;
+            }
+        }
+        result
+    }
+
+
+    /// Returns the backup hash, which for a file is its content hash.
+    fn apply(&self, file: &JournalFile) -> Result<Option<String>> {
+        let path = &file.original_path;
+        if file.action != JournalAction::Modify {
+            check_identity(path, file.identity)?;
+        }
+        let mut backup_hash = None;
+        if file.backup {
+            let (hash, _) = self.store.backup(self.conn, file.id, path)?;
+            self.conn.execute(
+                "UPDATE journal_files SET backup_hash = ?1 WHERE id = ?2",
+                params![hash, file.id],
+            )?;
+            backup_hash = Some(hash);
+        }
+
+        match file.action {
+            JournalAction::Trash => {
+                trash::delete(path).map_err(|e| CleanerError::System(e.to_string()))?;
+            }
+            JournalAction::SecureDelete => {
+                secure_delete(path, file.identity, &self.secure_delete, self.progress.as_deref())?
+            }
+            JournalAction::Quarantine => {
+                // Recorded before the rename so a crash in between can be reconciled
+                let destination = self.quarantine.destination(file.id, path)?;
+                self.conn.execute(
+                    "UPDATE journal_files SET quarantine_path = ?1 WHERE id = ?2",
+                    params![SqlPath(&destination), file.id],
+                )?;
+                self.quarantine.move_in(path, file.identity, &destination)?;
+            }
+            JournalAction::Delete => remove_tree(path, file.identity, self.progress.as_deref())?,
+            JournalAction::Modify => {}
+        }
+        Ok(backup_hash)
+    }
+
+    /// Leaves a pending file untouched, e.g. when the cleanup was cancelled
+    /// before it got to it.
+    pub fn skip(&self, file: &JournalFile, reason: &str) -> Result<()> {
+        self.mark_file(file.id, FileStatus::Skipped, Some(reason))?;
+        let event = AuditEvent::unhashed(audit_action(file.action), &file.original_path)
+            .rule(self.rules(), &file.original_path)
+            .operation(file.operation_id);
+        self.audit.record(event, AuditOutcome::Skipped, Some(reason))?;
+        Ok(())
+    }
+
+    pub fn commit(&self, operation_id: i64) -> Result<()> {
+        self.set_operation_status(operation_id, OperationStatus::Committed)
+    }
+
+    /// Journals a backup of a file that is about to be changed in place and
+    /// returns the journal file id, which doubles as the restore point id.
+    pub fn record_modification(&self, kind: &str, path: &Path) -> Result<i64> {
+        let operation_id = self.begin(kind, None, &[(path.to_path_buf(), JournalAction::Modify, true)])?;
+        let file = self
+            .files(operation_id)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| CleanerError::System("journal entry disappeared".to_string()))?;
+        self.execute(&file)?;
+        self.commit(operation_id)?;
+        Ok(file.id)
+    }
+
+    /// Journals an operation that already happened elsewhere, e.g. files the
+    /// caller moved to the trash itself.
+    pub fn record_completed(
+        &self,
+        kind: &str,
+        reference: Option<&str>,
+        files: &[(PathBuf, u64, JournalAction, DateTime<Utc>)],
+    ) -> Result<i64> {
+        let tx = self.conn.unchecked_transaction()?;
+        let now = Utc::now().to_rfc3339();
+        tx.execute(
+            "INSERT INTO journal_operations (kind, reference, status, started_at, finished_at) VALUES (?1, ?2, ?3, ?4, ?4)",
+            params![kind, reference, status_str(OperationStatus::Committed), now],
+        )?;
+        let operation_id = tx.last_insert_rowid();
+
+        for (path, size, action, processed_at) in files {
+            tx.execute(
+                "INSERT INTO journal_files (operation_id, original_path, action, file_size, status, processed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    operation_id,
+                    SqlPath(path),
+                    action_str(*action),
+                    *size as i64,
+                    file_status_str(FileStatus::Done),
+                    processed_at.to_rfc3339(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(operation_id)
+    }
+
+    pub fn operations(&self, limit: i64) -> Result<Vec<JournalOperation>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} GROUP BY o.id ORDER BY o.started_at DESC LIMIT ?1",
+            OPERATION_SELECT
+        ))?;
+        let operations = stmt
+            .query_map([limit], operation_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(operations)
+    }
+
+    pub fn operation(&self, operation_id: i64) -> Result<JournalOperation> {
+        self.conn
+            .query_row(
+                &format!("{} WHERE o.id = ?1 GROUP BY o.id", OPERATION_SELECT),
+                [operation_id],
+                operation_from_row,
+            )
+            .optional()?
+            .ok_or_else(|| CleanerError::FileNotFound(format!("journal operation {}", operation_id)))
+    }
+
+    pub fn files(&self, operation_id: i64) -> Result<Vec<JournalFile>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE operation_id = ?1 ORDER BY id",
+            FILE_SELECT
+        ))?;
+        let files = stmt
+            .query_map([operation_id], file_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(files)
+    }
+
+    pub fn file(&self, file_id: i64) -> Result<JournalFile> {
+        self.conn
+            .query_row(&format!("{} WHERE id = ?1", FILE_SELECT), [file_id], file_from_row)
+            .optional()?
+            .ok_or_else(|| CleanerError::FileNotFound(format!("journal file {}", file_id)))
+    }
+
+    pub fn files_with(&self, action: JournalAction, status: FileStatus) -> Result<Vec<JournalFile>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE action = ?1 AND status = ?2 ORDER BY processed_at DESC",
+            FILE_SELECT
+        ))?;
+        let files = stmt
+            .query_map([action_str(action), file_status_str(status)], file_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(files)
+    }
+
+    /// Files that have a backup and have not been restored yet.
+    pub fn restorable_files(&self) -> Result<Vec<JournalFile>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{} WHERE backup_hash IS NOT NULL AND status = ?1 ORDER BY processed_at DESC",
+            FILE_SELECT
+        ))?;
+        let files = stmt
+            .query_map([file_status_str(FileStatus::Done)], file_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(files)
+    }
+
+    /// Puts one file back, from its backup if it has one, otherwise from the trash.
+    pub fn restore_file(&self, file_id: i64, policy: &ConflictPolicy) -> Result<RestoredFile> {
+        let restored = self.restore_one(&self.file(file_id)?, policy)?;
+        self.store.remove_unreferenced(self.conn)?;
+        Ok(restored)
+    }
+
+    /// Restores the chosen files; one failing does not stop the others.
+    pub fn restore_files(&self, file_ids: &[i64], policy: &ConflictPolicy) -> Result<RestoreReport> {
+        let mut report = RestoreReport::default();
+        for &file_id in file_ids {
+            let file = self.file(file_id)?;
+            match self.restore_one(&file, policy) {
+                Ok(restored) => report.files.push(restored),
+                Err(e) => report.failed.push((file.original_path, e.to_string())),
+            }
+        }
+        self.store.remove_unreferenced(self.conn)?;
+        Ok(report)
+    }
+
+    /// Restores every processed file of an operation, newest first.
+    pub fn restore_operation(&self, operation_id: i64, policy: &ConflictPolicy) -> Result<RestoreReport> {
+        let ids: Vec<i64> = self
+            .files(operation_id)?
+            .into_iter()
+            .rev()
+            .filter(|f| f.status == FileStatus::Done)
+            .map(|f| f.id)
+            .collect();
+        self.restore_files(&ids, policy)
+    }
+
+    fn restore_one(&self, file: &JournalFile, policy: &ConflictPolicy) -> Result<RestoredFile> {
+        let event = AuditEvent::new(AuditAction::Restore, &file.original_path)
+            .operation(file.operation_id)
+            .size(file.size);
+        let result = self.try_restore(file, policy);
+        match &result {
+            Ok(restored) if restored.outcome == RestoreOutcome::Skipped => {
+                self.audit.record(event, AuditOutcome::Skipped, Some("a file is in the way"))?
+            }
+            Ok(_) => self.audit.record(event, AuditOutcome::Succeeded, None)?,
+            Err(e) => self.audit.record(event, AuditOutcome::Failed, Some(&e.to_string()))?,
+        }
+        result
+    }
+
+    fn try_restore(&self, file: &JournalFile, policy: &ConflictPolicy) -> Result<RestoredFile> {
+        if file.status != FileStatus::Done {
+            return Err(CleanerError::System(format!(
+                "{} cannot be restored ({:?})",
+                file.original_path.display(),
+                file.status
+            )));
+        }
+        if file.backup_hash.is_none() && !matches!(file.action, JournalAction::Trash | JournalAction::Quarantine) {
+            return Err(CleanerError::System(format!(
+                "{} was permanently deleted without a backup",
+                file.original_path.display()
+            )));
+        }
+
+        let wanted = match policy {
+            ConflictPolicy::RestoreTo(directory) => directory.join(file.original_path.file_name().unwrap_or_default()),
+            _ => file.original_path.clone(),
+        };
+        let conflict = fs::symlink_metadata(&wanted).is_ok();
+
+        let (target, outcome) = match (conflict, policy) {
+            (false, _) => (wanted, RestoreOutcome::Restored),
+            (true, ConflictPolicy::Skip) => {
+                return Ok(RestoredFile {
+                    id: file.id,
+                    original_path: file.original_path.clone(),
+                    restored_to: None,
+                    outcome: RestoreOutcome::Skipped,
+                })
+            }
+            (true, ConflictPolicy::Overwrite) => (wanted, RestoreOutcome::Overwritten),
+            (true, _) => (free_restore_path(&wanted), RestoreOutcome::Renamed),
+        };
+
+        // Move what is in the way aside, and back if the restore fails
+        let displaced = (outcome == RestoreOutcome::Overwritten).then(|| {
+            let mut name = std::ffi::OsString::from(".");
+            name.push(target.file_name().unwrap_or_default());
+            name.push(format!(".replaced-{}", file.id));
+            target.with_file_name(name)
+        });
+        if let Some(ref aside) = displaced {
+            fs::rename(&target, aside)?;
+        }
+
+        if let Err(e) = self.put_back(file, &target) {
+            if let Some(ref aside) = displaced {
+                remove_path(&target);
+                let _ = fs::rename(aside, &target);
+            }
+            return Err(e);
+        }
+        if let Some(ref aside) = displaced {
+            remove_path(aside);
+        }
+
+        self.mark_file(file.id, FileStatus::Restored, None)?;
+        Ok(RestoredFile {
+            id: file.id,
+            original_path: file.original_path.clone(),
+            restored_to: Some(target),
+            outcome,
+        })
+    }
+
+    fn put_back(&self, file: &JournalFile, target: &Path) -> Result<()> {
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if file.backup_hash.is_some() {
+            self.store.restore(self.conn, file.id, target)?;
+            self.store.discard(self.conn, file.id)
+        } else if let Some(ref quarantined) = file.quarantine_path {
+            self.quarantine.move_out(quarantined, target)
+        } else {
+            restore_from_trash_to(&file.original_path, file.processed_at, target)
+        }
+    }
+
+    /// Drops a file's backup manifest after retention expired it. The
+    /// objects are left for `BackupStore::remove_unreferenced`.
+    pub fn expire_backup(&self, file_id: i64) -> Result<()> {
+        self.store.discard(self.conn, file_id)?;
+        self.mark_file(file_id, FileStatus::Expired, None)
+    }
+
+    /// Marks a quarantined file as gone for good after it was purged.
+    pub fn expire_quarantined(&self, file_id: i64) -> Result<()> {
+        self.mark_file(file_id, FileStatus::Expired, None)
+    }
+
+    /// Restores every processed file of an operation, newest first.
+    pub fn rollback(&self, operation_id: i64) -> Result<RecoveryResult> {
+        let mut files_processed = 0;
+        let mut failed = Vec::new();
+
+        for file in self.files(operation_id)?.into_iter().rev() {
+            match file.status {
+                FileStatus::Done => {
+                    // Undoing an in-place change means putting the old contents back
+                    let policy = if file.action == JournalAction::Modify {
+                        ConflictPolicy::Overwrite
+                    } else {
+                        ConflictPolicy::KeepBoth
+                    };
+                    match self.restore_one(&file, &policy) {
+                        Ok(_) => files_processed += 1,
+                        Err(e) => failed.push((file.original_path.clone(), e.to_string())),
+                    }
+                }
+                FileStatus::Pending => {
+                    // A backup may have been started before the crash
+                    if file.backup {
+                        self.store.discard(self.conn, file.id)?;
+                    }
+                    self.mark_file(file.id, FileStatus::Skipped, None)?;
+                }
+                _ => {}
+            }
+        }
+
+        self.store.remove_unreferenced(self.conn)?;
+        let status = if failed.is_empty() { OperationStatus::RolledBack } else { self.operation(operation_id)?.status };
+        self.set_operation_status(operation_id, status)?;
+        Ok(RecoveryResult {
+            operation_id,
+            status,
+            files_processed,
+            failed,
+        })
+    }
+
+    /// Finds operations left pending by a crash and reconciles their files
+    /// with the disk: a pending file that is already gone was processed.
+    /// If it was to be backed up, the crash may have come before its backup
+    /// hash was recorded; that is rebuilt from the manifest, and a file
+    /// without one is marked failed. The operations are marked interrupted
+    /// until `recover` is called.
+    pub fn detect_interrupted(&self) -> Result<Vec<JournalOperation>> {
+        let pending: Vec<i64> = {
+            let mut stmt = self.conn.prepare("SELECT id FROM journal_operations WHERE status = ?1")?;
+            let ids = stmt
+                .query_map([status_str(OperationStatus::Pending)], |row| row.get(0))?
+                .filter_map(|r| r.ok())
+                .collect();
+            ids
+        };
+
+        for operation_id in &pending {
+            for file in self.files(*operation_id)? {
+                if file.status != FileStatus::Pending || file.action == JournalAction::Modify {
+                    continue;
+                }
+                if fs::symlink_metadata(&file.original_path).is_ok() {
+                    continue;
+                }
+                if file.backup && file.backup_hash.is_none() {
+                    let Some(hash) = self.store.manifest_hash(self.conn, file.id)? else {
+                        self.mark_file(file.id, FileStatus::Failed, Some("removed without a complete backup"))?;
+                        continue;
+                    };
+                    self.conn.execute(
+                        "UPDATE journal_files SET backup_hash = ?1 WHERE id = ?2",
+                        params![hash, file.id],
+                    )?;
+                }
+                self.mark_file(file.id, FileStatus::Done, None)?;
+            }
+            self.set_operation_status(*operation_id, OperationStatus::Interrupted)?;
+        }
+
+        self.interrupted()
+    }
+
+    pub fn interrupted(&self) -> Result<Vec<JournalOperation>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("{} WHERE o.status = ?1 GROUP BY o.id ORDER BY o.started_at", OPERATION_SELECT))?;
+        let operations = stmt
+            .query_map([status_str(OperationStatus::Interrupted)], operation_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(operations)
+    }
+
+    pub fn recover(&self, operation_id: i64, policy: RecoveryPolicy) -> Result<RecoveryResult> {
+        match policy {
+            RecoveryPolicy::Rollback => self.rollback(operation_id),
+            RecoveryPolicy::Resume => {
+                let mut files_processed = 0;
+                let mut failed = Vec::new();
+                for file in self.files(operation_id)?.into_iter().filter(|f| f.status == FileStatus::Pending) {
+                    match self.execute(&file) {
+                        Ok(()) => files_processed += 1,
+                        Err(e) => failed.push((file.original_path.clone(), e.to_string())),
+                    }
+                }
+                self.commit(operation_id)?;
+                Ok(RecoveryResult {
+                    operation_id,
+                    status: OperationStatus::Committed,
+                    files_processed,
+                    failed,
+                })
+            }
+        }
+    }
+
+    /// `processed_at` keeps the time the file was acted on; restoring or
+    /// skipping it later does not overwrite that.
+    fn mark_file(&self, file_id: i64, status: FileStatus, error: Option<&str>) -> Result<()> {
+        let processed_at = matches!(status, FileStatus::Done | FileStatus::Failed).then(|| Utc::now().to_rfc3339());
+        self.conn.execute(
+            "UPDATE journal_files SET status = ?1, error = ?2, processed_at = COALESCE(?3, processed_at) WHERE id = ?4",
+            params![file_status_str(status), error, processed_at, file_id],
+        )?;
+        Ok(())
+    }
+
+    fn set_operation_status(&self, operation_id: i64, status: OperationStatus) -> Result<()> {
+        let finished_at = matches!(status, OperationStatus::Committed | OperationStatus::RolledBack)
+            .then(|| Utc::now().to_rfc3339());
+        self.conn.execute(
+            "UPDATE journal_operations SET status = ?1, finished_at = ?2 WHERE id = ?3",
+            params![status_str(status), finished_at, operation_id],
+        )?;
+        Ok(())
+    }
+}
+
+const OPERATION_SELECT: &str = "SELECT o.id, o.kind, o.reference, o.status, o.started_at, o.finished_at,
+        COUNT(f.id), COALESCE(SUM(f.file_size), 0)
+    FROM journal_operations o LEFT JOIN journal_files f ON f.operation_id = o.id";
+
+const FILE_SELECT: &str = "SELECT id, operation_id, original_path, action, backup, backup_hash, file_size, status,
+        processed_at, error, quarantine_path, device, inode
+    FROM journal_files";
+
+fn operation_from_row(row: &Row) -> rusqlite::Result<JournalOperation> {
+    Ok(JournalOperation {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        reference: row.get(2)?,
+        status: parse_status(&row.get::<_, String>(3)?),
+        started_at: parse_time(&row.get::<_, String>(4)?).unwrap_or_else(Utc::now),
+        finished_at: row.get::<_, Option<String>>(5)?.and_then(|t| parse_time(&t)),
+        files_count: row.get::<_, i64>(6)? as u64,
+        total_size: row.get::<_, i64>(7)? as u64,
+    })
+}
+
+fn file_from_row(row: &Row) -> rusqlite::Result<JournalFile> {
+    Ok(JournalFile {
+        id: row.get(0)?,
+        operation_id: row.get(1)?,
+        original_path: row.get::<_, RawPath>(2)?.into(),
+        action: parse_action(&row.get::<_, String>(3)?),
+        backup: row.get(4)?,
+        backup_hash: row.get(5)?,
+        size: row.get::<_, i64>(6)? as u64,
+        status: parse_file_status(&row.get::<_, String>(7)?),
+        processed_at: row.get::<_, Option<String>>(8)?.and_then(|t| parse_time(&t)),
+        error: row.get(9)?,
+        quarantine_path: row.get::<_, Option<RawPath>>(10)?.map(PathBuf::from),
+        identity: match (row.get::<_, Option<i64>>(11)?, row.get::<_, Option<i64>>(12)?) {
+            (Some(device), Some(inode)) => Some(FileIdentity {
+                device: device as u64,
+                inode: inode as u64,
+            }),
+            _ => None,
+        },
+    })
+}
+
+/// Catches a path that was replaced (e.g. by a symlink) after it was
+/// planned. Deletes check again, race-free, while they run.
+fn check_identity(path: &Path, expected: Option<FileIdentity>) -> Result<()> {
+    let Some(expected) = expected else { return Ok(()) };
+    if FileIdentity::of(&fs::symlink_metadata(path)?) != Some(expected) {
+        return Err(CleanerError::Changed(path.display().to_string()));
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) {
+    let _ = match fs::symlink_metadata(path) {
+        Ok(m) if m.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(_) => return,
+    };
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn action_str(action: JournalAction) -> &'static str {
+    match action {
+        JournalAction::Trash => "trash",
+        JournalAction::Delete => "delete",
+        JournalAction::SecureDelete => "secure_delete",
+        JournalAction::Quarantine => "quarantine",
+        JournalAction::Modify => "modify",
+    }
+}
+
+fn audit_action(action: JournalAction) -> AuditAction {
+    match action {
+        JournalAction::Trash => AuditAction::Trash,
+        JournalAction::Delete => AuditAction::Delete,
+        JournalAction::SecureDelete => AuditAction::SecureDelete,
+        JournalAction::Quarantine => AuditAction::Quarantine,
+        JournalAction::Modify => AuditAction::Modify,
+    }
+}
+
+fn parse_action(value: &str) -> JournalAction {
+    match value {
+        "trash" => JournalAction::Trash,
+        "secure_delete" => JournalAction::SecureDelete,
+        "quarantine" => JournalAction::Quarantine,
+        "modify" => JournalAction::Modify,
+        _ => JournalAction::Delete,
+    }
+}
+
+fn status_str(status: OperationStatus) -> &'static str {
+    match status {
+        OperationStatus::Pending => "pending",
+        OperationStatus::Committed => "committed",
+        OperationStatus::Interrupted => "interrupted",
+        OperationStatus::RolledBack => "rolled_back",
+    }
+}
+
+fn parse_status(value: &str) -> OperationStatus {
+    match value {
+        "committed" => OperationStatus::Committed,
+        "interrupted" => OperationStatus::Interrupted,
+        "rolled_back" => OperationStatus::RolledBack,
+        _ => OperationStatus::Pending,
+    }
+}
+
+fn file_status_str(status: FileStatus) -> &'static str {
+    match status {
+        FileStatus::Pending => "pending",
+        FileStatus::Done => "done",
+        FileStatus::Skipped => "skipped",
+        FileStatus::Failed => "failed",
+        FileStatus::Restored => "restored",
+        FileStatus::Expired => "expired",
+    }
+}
+
+fn parse_file_status(value: &str) -> FileStatus {
+    match value {
+        "done" => FileStatus::Done,
+        "skipped" => FileStatus::Skipped,
+        "failed" => FileStatus::Failed,
+        "restored" => FileStatus::Restored,
+        "expired" => FileStatus::Expired,
+        _ => FileStatus::Pending,
+    }
+}
+
+/// Run once at startup: marks operations a crash left pending as
+/// interrupted so the user can resume or roll them back.
+pub fn detect_interrupted_operations(db_path: &Path) -> Result<Vec<JournalOperation>> {
+    let conn = Connection::open(db_path)?;
+    let app_data_dir = db_path.parent().unwrap_or_else(|| Path::new("."));
+    Journal::new(&conn, app_data_dir).detect_interrupted()
+}
@@ -2,7 +2,35 @@
 pub mod safe_delete;
 pub mod restore;
 pub mod secure_delete;
+pub mod free_space;
+pub mod privacy;
+pub mod recent_files;
+pub mod trash_bin;
+pub mod backup_store;
+pub mod journal;
+pub mod retention;
+pub mod audit;
+pub mod history;
+pub mod quarantine;
+pub mod reclaim;
+pub mod executor;
+pub mod unlink;
+pub mod confirmation;
 
 pub use safe_delete::*;
 pub use restore::*;
 pub use secure_delete::*;
+pub use free_space::*;
+pub use privacy::*;
+pub use recent_files::*;
+pub use trash_bin::*;
+pub use backup_store::*;
+pub use journal::*;
+pub use retention::*;
+pub use audit::*;
+pub use history::*;
+pub use quarantine::*;
+pub use reclaim::*;
+pub use executor::*;
+pub use unlink::*;
+pub use confirmation::*;
@@ -0,0 +1,484 @@
+// src-tauri/src/cleanup/privacy.rs
+use super::safe_delete::create_restore_point;
+use crate::error::{CleanerError, Result};
+use crate::paths::serde_path;
+use crate::scanner::{BrowserKind, BrowserProfile, BrowserScanner};
+use glob::Pattern;
+use rusqlite::{params, Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Seconds between 1601-01-01 (Chromium's epoch) and 1970-01-01.
+const CHROMIUM_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyOptions {
+    pub dry_run: bool,
+    /// Delete browser history older than this many days.
+    pub history_older_than_days: Option<u32>,
+    pub clear_cookies: bool,
+    /// Cookies for these domains (and their subdomains) are kept.
+    pub cookie_whitelist: Vec<String>,
+    /// Drop shell history entries older than this many days.
+    pub shell_history_older_than_days: Option<u32>,
+    /// Drop shell history entries whose command matches one of these globs.
+    pub shell_history_patterns: Vec<String>,
+}
+
+impl Default for PrivacyOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            history_older_than_days: Some(90),
+            clear_cookies: false,
+            cookie_whitelist: vec![],
+            shell_history_older_than_days: None,
+            shell_history_patterns: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PrivacyTarget {
+    BrowserHistory,
+    Cookies,
+    ShellHistory,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyCleanItem {
+    pub target: PrivacyTarget,
+    /// e.g. "Firefox (default)" or "bash"
+    pub source: String,
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    pub entries_removed: u64,
+    pub restore_point_id: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacyCleanResult {
+    pub items: Vec<PrivacyCleanItem>,
+    pub entries_removed: u64,
+    pub was_dry_run: bool,
+}
+
+pub struct PrivacyCleaner {
+    options: PrivacyOptions,
+    app_data_dir: PathBuf,
+}
+
+impl PrivacyCleaner {
+    pub fn new(options: PrivacyOptions, app_data_dir: &Path) -> Self {
+        Self {
+            options,
+            app_data_dir: app_data_dir.to_path_buf(),
+        }
+    }
+
+    pub fn clean(&self, db_conn: &Connection) -> Result<PrivacyCleanResult> {
+        let mut items = Vec::new();
+
+        for profile in BrowserScanner::discover_profiles() {
+            let source = format!("{:?} ({})", profile.browser, profile.name);
+
+            for (target, path) in privacy_databases(&profile) {
+                let wanted = match target {
+                    PrivacyTarget::BrowserHistory => self.options.history_older_than_days.is_some(),
+                    PrivacyTarget::Cookies => self.options.clear_cookies,
+                    PrivacyTarget::ShellHistory => false,
+                };
+                if !wanted {
+                    continue;
+                }
+
+                let mut item = PrivacyCleanItem {
+                    target: target.clone(),
+                    source: source.clone(),
+                    path: path.clone(),
+                    entries_removed: 0,
+                    restore_point_id: None,
+                    error: None,
+                };
+
+                if profile.running {
+                    item.error = Some(format!("{:?} is running; close it before cleaning", profile.browser));
+                } else {
+                    match self.clean_database(&profile.browser, &target, &path, db_conn) {
+                        Ok((removed, restore_point_id)) => {
+                            item.entries_removed = removed;
+                            item.restore_point_id = restore_point_id;
+                        }
+                        Err(e) => item.error = Some(e.to_string()),
+                    }
+                }
+                items.push(item);
+            }
+        }
+
+        if self.options.shell_history_older_than_days.is_some() || !self.options.shell_history_patterns.is_empty() {
+            for (shell, path) in shell_history_files() {
+                let mut item = PrivacyCleanItem {
+                    target: PrivacyTarget::ShellHistory,
+                    source: shell.name().to_string(),
+                    path: path.clone(),
+                    entries_removed: 0,
+                    restore_point_id: None,
+                    error: None,
+                };
+                match self.clean_shell_history(shell, &path, db_conn) {
+                    Ok((removed, restore_point_id)) => {
+                        item.entries_removed = removed;
+                        item.restore_point_id = restore_point_id;
+                    }
+                    Err(e) => item.error = Some(e.to_string()),
+                }
+                items.push(item);
+            }
+        }
+
+        Ok(PrivacyCleanResult {
+            entries_removed: items.iter().map(|i| i.entries_removed).sum(),
+            items,
+            was_dry_run: self.options.dry_run,
+        })
+    }
+
+    /// Runs the deletes in a transaction. Dry runs roll it back, so the
+    /// reported counts are exact either way.
+    fn clean_database(
+        &self,
+        browser: &BrowserKind,
+        target: &PrivacyTarget,
+        path: &Path,
+        db_conn: &Connection,
+    ) -> Result<(u64, Option<i64>)> {
+        let mut conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+
+        let mut restore_point_id = None;
+        if !self.options.dry_run {
+            // Fold any WAL into the main file so the backup is complete
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+            restore_point_id = Some(create_restore_point(&self.app_data_dir, path, db_conn)?);
+        }
+
+        let tx = conn.transaction()?;
+        let removed = match (browser, target) {
+            (BrowserKind::Firefox, PrivacyTarget::BrowserHistory) => {
+                let cutoff = self.history_cutoff_micros(0);
+                delete_firefox_history(&tx, cutoff)?
+            }
+            (_, PrivacyTarget::BrowserHistory) => {
+                let cutoff = self.history_cutoff_micros(CHROMIUM_EPOCH_OFFSET_SECS);
+                delete_chromium_history(&tx, cutoff)?
+            }
+            (BrowserKind::Firefox, PrivacyTarget::Cookies) => {
+                delete_cookies(&tx, "moz_cookies", "id", "host", &self.options.cookie_whitelist)?
+            }
+            (_, PrivacyTarget::Cookies) => {
+                delete_cookies(&tx, "cookies", "rowid", "host_key", &self.options.cookie_whitelist)?
+            }
+            (_, PrivacyTarget::ShellHistory) => 0,
+        };
+
+        if self.options.dry_run {
+            tx.rollback()?;
+        } else {
+            tx.commit()?;
+            conn.execute_batch("VACUUM")?;
+        }
+
+        Ok((removed, restore_point_id))
+    }
+
+    /// Cutoff in microseconds since `epoch_offset_secs` before the Unix epoch.
+    fn history_cutoff_micros(&self, epoch_offset_secs: i64) -> i64 {
+        let days = self.options.history_older_than_days.unwrap_or(0) as i64;
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(days);
+        (cutoff.timestamp() + epoch_offset_secs) * 1_000_000
+    }
+
+    fn clean_shell_history(&self, shell: Shell, path: &Path, db_conn: &Connection) -> Result<(u64, Option<i64>)> {
+        let content = fs::read(path)?;
+        let entries = shell.parse(&content);
+
+        let patterns: Vec<Pattern> = self
+            .options
+            .shell_history_patterns
+            .iter()
+            .filter_map(|p| Pattern::new(p).ok())
+            .collect();
+        let cutoff = self
+            .options
+            .shell_history_older_than_days
+            .map(|days| (chrono::Utc::now() - chrono::Duration::days(days as i64)).timestamp());
+
+        let mut kept = Vec::with_capacity(content.len());
+        let mut removed = 0u64;
+        for entry in &entries {
+            let too_old = match (cutoff, entry.timestamp) {
+                (Some(cutoff), Some(ts)) => ts < cutoff,
+                _ => false,
+            };
+            let command = String::from_utf8_lossy(&entry.command);
+            let matches = patterns.iter().any(|p| p.matches(command.trim()));
+
+            if too_old || matches {
+                removed += 1;
+            } else {
+                kept.extend_from_slice(&entry.raw);
+            }
+        }
+
+        if removed == 0 || self.options.dry_run {
+            return Ok((removed, None));
+        }
+
+        let restore_point_id = create_restore_point(&self.app_data_dir, path, db_conn)?;
+        write_atomically(path, &kept)?;
+        Ok((removed, Some(restore_point_id)))
+    }
+}
+
+/// (target, database file) pairs for one browser profile.
+fn privacy_databases(profile: &BrowserProfile) -> Vec<(PrivacyTarget, PathBuf)> {
+    let candidates = match profile.browser {
+        BrowserKind::Firefox => vec![
+            (PrivacyTarget::BrowserHistory, profile.path.join("places.sqlite")),
+            (PrivacyTarget::Cookies, profile.path.join("cookies.sqlite")),
+        ],
+        _ => vec![
+            (PrivacyTarget::BrowserHistory, profile.path.join("History")),
+            // Chromium 96+ moved cookies under Network/
+            (PrivacyTarget::Cookies, profile.path.join("Network/Cookies")),
+            (PrivacyTarget::Cookies, profile.path.join("Cookies")),
+        ],
+    };
+
+    candidates.into_iter().filter(|(_, p)| p.is_file()).collect()
+}
+
+fn delete_firefox_history(conn: &Connection, cutoff_micros: i64) -> Result<u64> {
+    let removed = conn.execute(
+        "DELETE FROM moz_historyvisits WHERE visit_date < ?1",
+        params![cutoff_micros],
+    )?;
+
+    // Bookmarked and otherwise referenced places keep their row
+    conn.execute(
+        "DELETE FROM moz_places
+         WHERE foreign_count = 0
+           AND id NOT IN (SELECT place_id FROM moz_historyvisits)",
+        [],
+    )?;
+    conn.execute(
+        "UPDATE moz_places SET
+             visit_count = (SELECT COUNT(*) FROM moz_historyvisits v WHERE v.place_id = moz_places.id),
+             last_visit_date = (SELECT MAX(visit_date) FROM moz_historyvisits v WHERE v.place_id = moz_places.id)",
+        [],
+    )?;
+    // Auxiliary tables differ between Firefox versions
+    let _ = conn.execute("DELETE FROM moz_origins WHERE id NOT IN (SELECT origin_id FROM moz_places)", []);
+    let _ = conn.execute("DELETE FROM moz_inputhistory WHERE place_id NOT IN (SELECT id FROM moz_places)", []);
+
+    Ok(removed as u64)
+}
+
+fn delete_chromium_history(conn: &Connection, cutoff_micros: i64) -> Result<u64> {
+    let removed = conn.execute("DELETE FROM visits WHERE visit_time < ?1", params![cutoff_micros])?;
+
+    conn.execute("DELETE FROM urls WHERE id NOT IN (SELECT url FROM visits)", [])?;
+    conn.execute(
+        "UPDATE urls SET visit_count = (SELECT COUNT(*) FROM visits WHERE visits.url = urls.id)",
+        [],
+    )?;
+    // Auxiliary tables differ between Chromium versions
+    let _ = conn.execute("DELETE FROM keyword_search_terms WHERE url_id NOT IN (SELECT id FROM urls)", []);
+    let _ = conn.execute("DELETE FROM segment_usage WHERE segment_id NOT IN (SELECT id FROM segments)", []);
+    let _ = conn.execute("DELETE FROM visit_source WHERE id NOT IN (SELECT id FROM visits)", []);
+
+    Ok(removed as u64)
+}
+
+fn delete_cookies(conn: &Connection, table: &str, id_column: &str, host_column: &str, whitelist: &[String]) -> Result<u64> {
+    let mut stmt = conn.prepare(&format!("SELECT {}, {} FROM {}", id_column, host_column, table))?;
+    let doomed: Vec<i64> = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .filter_map(|r| r.ok())
+        .filter(|(_, host)| !is_whitelisted(host, whitelist))
+        .map(|(id, _)| id)
+        .collect();
+    drop(stmt);
+
+    let mut delete = conn.prepare(&format!("DELETE FROM {} WHERE {} = ?1", table, id_column))?;
+    for id in &doomed {
+        delete.execute(params![id])?;
+    }
+
+    Ok(doomed.len() as u64)
+}
+
+/// `.example.com` and `login.example.com` are both covered by `example.com`.
+fn is_whitelisted(host: &str, whitelist: &[String]) -> bool {
+    let host = host.trim_start_matches('.').to_lowercase();
+    whitelist.iter().any(|domain| {
+        let domain = domain.trim_start_matches('.').to_lowercase();
+        host == domain || host.ends_with(&format!(".{}", domain))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// One history entry with its exact on-disk bytes, so kept entries are
+/// written back unchanged (zsh metafies non-ASCII bytes).
+struct HistoryEntry {
+    raw: Vec<u8>,
+    command: Vec<u8>,
+    timestamp: Option<i64>,
+}
+
+impl Shell {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+
+    fn parse(&self, content: &[u8]) -> Vec<HistoryEntry> {
+        let lines: Vec<&[u8]> = content.split_inclusive(|b| *b == b'\n').collect();
+        let mut entries: Vec<HistoryEntry> = Vec::new();
+
+        match self {
+            // With HISTTIMEFORMAT set, bash writes "#<epoch>" before each command
+            Shell::Bash => {
+                let mut pending_ts: Option<(i64, &[u8])> = None;
+                for line in lines {
+                    let text = trim_newline(line);
+                    if let Some(ts) = text.strip_prefix(b"#").and_then(parse_epoch) {
+                        pending_ts = Some((ts, line));
+                        continue;
+                    }
+                    let mut raw = Vec::new();
+                    let timestamp = pending_ts.take().map(|(ts, ts_line)| {
+                        raw.extend_from_slice(ts_line);
+                        ts
+                    });
+                    raw.extend_from_slice(line);
+                    entries.push(HistoryEntry { raw, command: text.to_vec(), timestamp });
+                }
+            }
+            // Extended format ": <epoch>:<duration>;<command>", continued with trailing backslashes
+            Shell::Zsh => {
+                let mut continues = false;
+                for line in lines {
+                    let text = trim_newline(line);
+                    if continues {
+                        if let Some(last) = entries.last_mut() {
+                            last.raw.extend_from_slice(line);
+                            last.command.push(b'\n');
+                            last.command.extend_from_slice(text);
+                        }
+                    } else {
+                        let (timestamp, command) = match text.strip_prefix(b": ") {
+                            Some(rest) => {
+                                let split = rest.iter().position(|b| *b == b';');
+                                let ts = split.and_then(|i| rest[..i].split(|b| *b == b':').next().and_then(parse_epoch));
+                                (ts, split.map(|i| &rest[i + 1..]).unwrap_or(rest))
+                            }
+                            None => (None, text),
+                        };
+                        entries.push(HistoryEntry { raw: line.to_vec(), command: command.to_vec(), timestamp });
+                    }
+                    continues = text.ends_with(b"\\");
+                }
+            }
+            // "- cmd: <command>" followed by indented "when:" and "paths:" lines
+            Shell::Fish => {
+                for line in lines {
+                    let text = trim_newline(line);
+                    if let Some(command) = text.strip_prefix(b"- cmd: ") {
+                        entries.push(HistoryEntry { raw: line.to_vec(), command: command.to_vec(), timestamp: None });
+                    } else if let Some(last) = entries.last_mut() {
+                        last.raw.extend_from_slice(line);
+                        if let Some(ts) = text.trim_ascii_start().strip_prefix(b"when: ").and_then(parse_epoch) {
+                            last.timestamp = Some(ts);
+                        }
+                    } else {
+                        entries.push(HistoryEntry { raw: line.to_vec(), command: vec![], timestamp: None });
+                    }
+                }
+            }
+        }
+
+        entries
+    }
+}
+
+fn shell_history_files() -> Vec<(Shell, PathBuf)> {
+    let Some(home) = dirs::home_dir() else { return vec![] };
+    let mut files = vec![
+        (Shell::Bash, home.join(".bash_history")),
+        (Shell::Zsh, home.join(".zsh_history")),
+        (Shell::Zsh, home.join(".histfile")),
+    ];
+    if let Some(data) = dirs::data_dir() {
+        files.push((Shell::Fish, data.join("fish/fish_history")));
+    }
+
+    // $HISTFILE is only exported by some setups, but honor it when it is
+    if let Some(histfile) = std::env::var_os("HISTFILE").map(PathBuf::from) {
+        let shell = match std::env::var("SHELL").unwrap_or_default() {
+            s if s.ends_with("zsh") => Shell::Zsh,
+            _ => Shell::Bash,
+        };
+        if !files.iter().any(|(_, p)| *p == histfile) {
+            files.push((shell, histfile));
+        }
+    }
+
+    files.into_iter().filter(|(_, p)| p.is_file()).collect()
+}
+
+fn trim_newline(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\n").unwrap_or(line)
+}
+
+fn parse_epoch(bytes: &[u8]) -> Option<i64> {
+    std::str::from_utf8(bytes).ok()?.trim().parse().ok()
+}
+
+/// Replaces `path` with `data` via a temporary file in the same directory,
+/// keeping the original permissions.
+pub(crate) fn write_atomically(path: &Path, data: &[u8]) -> Result<()> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| CleanerError::System(format!("{} has no parent directory", path.display())))?;
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(path.file_name().unwrap_or_default());
+    tmp_name.push(".systemsweep-tmp");
+    let tmp_path = dir.join(tmp_name);
+
+    let mut tmp = fs::File::create(&tmp_path)?;
+    if let Ok(metadata) = fs::metadata(path) {
+        tmp.set_permissions(metadata.permissions())?;
+    }
+    tmp.write_all(data)?;
+    tmp.sync_all()?;
+    drop(tmp);
+
+    if let Err(e) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
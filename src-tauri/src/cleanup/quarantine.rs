@@ -0,0 +1,225 @@
+// src-tauri/src/cleanup/quarantine.rs
+use super::audit::{AuditAction, AuditEvent, AuditLog, AuditOutcome};
+use super::journal::{FileStatus, Journal, JournalAction, JournalFile};
+use super::trash_bin::copy_recursively;
+use super::unlink::{remove_tree, rename_checked};
+use crate::error::{CleanerError, Result};
+use crate::paths::serde_path;
+use crate::scanner::FileIdentity;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const QUARANTINE_DIR: &str = "quarantine";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinedItem {
+    /// Journal file id; restores and purges refer to it.
+    pub id: i64,
+    pub operation_id: i64,
+    #[serde(with = "serde_path")]
+    pub original_path: PathBuf,
+    #[serde(with = "serde_path")]
+    pub quarantine_path: PathBuf,
+    pub size: u64,
+    pub quarantined_at: Option<DateTime<Utc>>,
+    /// When the automatic purge removes it for good.
+    pub purge_after: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantinePurgeResult {
+    pub purged: Vec<QuarantinedItem>,
+    #[serde(with = "serde_path::failures")]
+    pub failed: Vec<(PathBuf, String)>,
+    pub bytes_freed: u64,
+    pub was_dry_run: bool,
+}
+
+/// Items are renamed into a quarantine directory on their own filesystem,
+/// so quarantining never copies data. That is `<app data>/quarantine` when
+/// it shares the filesystem, otherwise `.systemsweep-quarantine-$UID` at
+/// the top of the mount, like the trash spec's `.Trash-$UID`.
+pub struct Quarantine {
+    app_data_dir: PathBuf,
+}
+
+impl Quarantine {
+    pub fn new(app_data_dir: &Path) -> Self {
+        Self {
+            app_data_dir: app_data_dir.to_path_buf(),
+        }
+    }
+
+    /// Where `path` goes for journal file `file_id`. Nothing is moved yet,
+    /// so the journal can record the destination before the rename.
+    pub fn destination(&self, file_id: i64, path: &Path) -> Result<PathBuf> {
+        let name = path
+            .file_name()
+            .ok_or_else(|| CleanerError::System(format!("{} cannot be quarantined", path.display())))?;
+        Ok(self.root_for(path)?.join(file_id.to_string()).join(name))
+    }
+
+    /// Renames `path` to `destination` if it still is the `expected` file.
+    /// Fails instead of copying when the rename crosses filesystems.
+    pub fn move_in(&self, path: &Path, expected: Option<FileIdentity>, destination: &Path) -> Result<()> {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        rename_checked(path, expected, destination)
+    }
+
+    /// Moves a quarantined item to `target`; restoring to another
+    /// filesystem falls back to a copy.
+    pub fn move_out(&self, quarantine_path: &Path, target: &Path) -> Result<()> {
+        if fs::rename(quarantine_path, target).is_err() {
+            copy_recursively(quarantine_path, target)?;
+            remove_path(quarantine_path)?;
+        }
+        remove_empty_parent(quarantine_path);
+        Ok(())
+    }
+
+    pub fn remove(&self, quarantine_path: &Path) -> Result<()> {
+        if fs::symlink_metadata(quarantine_path).is_ok() {
+            remove_path(quarantine_path)?;
+        }
+        remove_empty_parent(quarantine_path);
+        Ok(())
+    }
+
+    /// Items still in quarantine, oldest first.
+    pub fn items(&self, conn: &Connection, grace_days: u32) -> Result<Vec<QuarantinedItem>> {
+        let mut items: Vec<QuarantinedItem> = Journal::new(conn, &self.app_data_dir)
+            .files_with(JournalAction::Quarantine, FileStatus::Done)?
+            .into_iter()
+            .filter_map(|file| item(file, grace_days))
+            .collect();
+        items.sort_by_key(|i| i.quarantined_at);
+        Ok(items)
+    }
+
+    /// Space that quarantined items still take, per quarantine path. It is
+    /// released once they are purged.
+    pub fn pending_reclaim(&self, conn: &Connection) -> Result<Vec<(PathBuf, u64)>> {
+        Ok(self
+            .items(conn, 0)?
+            .into_iter()
+            .map(|i| (i.quarantine_path, i.size))
+            .collect())
+    }
+
+    /// Permanently deletes items quarantined more than `grace_days` ago.
+    pub fn purge(&self, conn: &Connection, grace_days: u32, dry_run: bool) -> Result<QuarantinePurgeResult> {
+        let journal = Journal::new(conn, &self.app_data_dir);
+        let audit = AuditLog::new(&self.app_data_dir);
+        let now = Utc::now();
+        let mut result = QuarantinePurgeResult {
+            purged: Vec::new(),
+            failed: Vec::new(),
+            bytes_freed: 0,
+            was_dry_run: dry_run,
+        };
+
+        for item in self.items(conn, grace_days)? {
+            if item.purge_after.map(|t| t > now).unwrap_or(true) {
+                continue;
+            }
+            if !dry_run {
+                let event = AuditEvent::new(AuditAction::Delete, &item.quarantine_path)
+                    .operation(item.operation_id)
+                    .size(item.size);
+                if let Err(e) = self.remove(&item.quarantine_path) {
+                    audit.record(event, AuditOutcome::Failed, Some(&e.to_string()))?;
+                    result.failed.push((item.original_path, e.to_string()));
+                    continue;
+                }
+                audit.record(event, AuditOutcome::Succeeded, None)?;
+                journal.expire_quarantined(item.id)?;
+            }
+            result.bytes_freed += item.size;
+            result.purged.push(item);
+        }
+        Ok(result)
+    }
+
+    fn root_for(&self, path: &Path) -> Result<PathBuf> {
+        let app_root = self.app_data_dir.join(QUARANTINE_DIR);
+        #[cfg(unix)]
+        {
+            let device = device_of(path.parent().unwrap_or(path))?;
+            if fs::create_dir_all(&app_root).is_ok() && device_of(&app_root).ok() == Some(device) {
+                return Ok(app_root);
+            }
+
+            let mount = crate::system::mounts::mount_for(path)
+                .ok_or_else(|| CleanerError::System(format!("No mount found for {}", path.display())))?;
+            let root = mount
+                .mount_point
+                .join(format!(".systemsweep-quarantine-{}", unsafe { libc::getuid() }));
+            create_private_dir(&root)?;
+            if device_of(&root)? != device {
+                return Err(CleanerError::System(format!(
+                    "No quarantine directory on the filesystem of {}",
+                    path.display()
+                )));
+            }
+            Ok(root)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            Ok(app_root)
+        }
+    }
+}
+
+fn item(file: JournalFile, grace_days: u32) -> Option<QuarantinedItem> {
+    Some(QuarantinedItem {
+        id: file.id,
+        operation_id: file.operation_id,
+        original_path: file.original_path,
+        quarantine_path: file.quarantine_path?,
+        size: file.size,
+        quarantined_at: file.processed_at,
+        purge_after: file.processed_at.map(|t| t + Duration::days(grace_days as i64)),
+    })
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    remove_tree(path, None, None)
+}
+
+/// Drops the per-item `<id>` directory once it is empty.
+fn remove_empty_parent(quarantine_path: &Path) {
+    if let Some(parent) = quarantine_path.parent() {
+        let _ = fs::remove_dir(parent);
+    }
+}
+
+#[cfg(unix)]
+fn device_of(path: &Path) -> Result<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Ok(fs::metadata(path)?.dev())
+}
+
+/// Creates the per-user directory at the top of a mount, readable only by
+/// its owner.
+#[cfg(unix)]
+fn create_private_dir(path: &Path) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+    match fs::DirBuilder::new().mode(0o700).create(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            let metadata = fs::symlink_metadata(path)?;
+            // Someone else's directory (or a symlink) under a shared mount point
+            if !metadata.is_dir() || metadata.uid() != unsafe { libc::getuid() } {
+                return Err(CleanerError::PermissionDenied(path.display().to_string()));
+            }
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
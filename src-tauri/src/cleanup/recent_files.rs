@@ -0,0 +1,285 @@
+// src-tauri/src/cleanup/recent_files.rs
+use super::privacy::write_atomically;
+use super::safe_delete::create_restore_point;
+use crate::error::Result;
+use crate::paths::{path_from_bytes, serde_path};
+use chrono::{DateTime, Utc};
+use glob::Pattern;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFilesOptions {
+    pub dry_run: bool,
+    /// Drop `file://` entries whose target no longer exists.
+    pub remove_missing: bool,
+    /// Drop entries not modified or visited in this many days.
+    pub older_than_days: Option<u32>,
+    /// Drop entries whose path (or URI, for non-local entries) matches one of these globs.
+    pub patterns: Vec<String>,
+}
+
+impl Default for RecentFilesOptions {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            remove_missing: true,
+            older_than_days: None,
+            patterns: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentEntry {
+    pub href: String,
+    /// Local path for `file://` URIs.
+    #[serde(with = "serde_path::option")]
+    pub path: Option<PathBuf>,
+    pub added: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub visited: Option<DateTime<Utc>>,
+}
+
+impl RecentEntry {
+    /// Most recent of the three XBEL timestamps.
+    pub fn last_used(&self) -> Option<DateTime<Utc>> {
+        [self.added, self.modified, self.visited].into_iter().flatten().max()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedRecentEntry {
+    pub entry: RecentEntry,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFilesResult {
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    pub entries_total: u64,
+    pub removed: Vec<RemovedRecentEntry>,
+    pub restore_point_id: Option<i64>,
+    pub was_dry_run: bool,
+}
+
+/// A `<bookmark>` element and its byte range in the file, including the
+/// indentation in front of it so removal leaves no blank line behind.
+struct BookmarkBlock {
+    start: usize,
+    end: usize,
+    entry: RecentEntry,
+}
+
+pub struct RecentFilesCleaner {
+    options: RecentFilesOptions,
+    app_data_dir: PathBuf,
+}
+
+impl RecentFilesCleaner {
+    pub fn new(options: RecentFilesOptions, app_data_dir: &Path) -> Self {
+        Self {
+            options,
+            app_data_dir: app_data_dir.to_path_buf(),
+        }
+    }
+
+    pub fn clean(&self, db_conn: &Connection) -> Result<Option<RecentFilesResult>> {
+        let Some(path) = recently_used_path().filter(|p| p.is_file()) else {
+            return Ok(None);
+        };
+
+        let content = fs::read_to_string(&path)?;
+        let blocks = parse_bookmarks(&content);
+        let patterns: Vec<Pattern> = self.options.patterns.iter().filter_map(|p| Pattern::new(p).ok()).collect();
+        let cutoff = self
+            .options
+            .older_than_days
+            .map(|days| Utc::now() - chrono::Duration::days(days as i64));
+
+        let mut kept = String::with_capacity(content.len());
+        let mut removed = Vec::new();
+        let mut copied_up_to = 0;
+
+        for block in &blocks {
+            let Some(reason) = self.removal_reason(&block.entry, cutoff, &patterns) else { continue };
+            kept.push_str(&content[copied_up_to..block.start]);
+            copied_up_to = block.end;
+            removed.push(RemovedRecentEntry {
+                entry: block.entry.clone(),
+                reason,
+            });
+        }
+        kept.push_str(&content[copied_up_to..]);
+
+        let mut restore_point_id = None;
+        if !removed.is_empty() && !self.options.dry_run {
+            restore_point_id = Some(create_restore_point(&self.app_data_dir, &path, db_conn)?);
+            write_atomically(&path, kept.as_bytes())?;
+        }
+
+        Ok(Some(RecentFilesResult {
+            path,
+            entries_total: blocks.len() as u64,
+            removed,
+            restore_point_id,
+            was_dry_run: self.options.dry_run,
+        }))
+    }
+
+    fn removal_reason(&self, entry: &RecentEntry, cutoff: Option<DateTime<Utc>>, patterns: &[Pattern]) -> Option<String> {
+        if self.options.remove_missing {
+            if let Some(ref path) = entry.path {
+                // symlink_metadata so a dangling link still counts as present
+                if fs::symlink_metadata(path).is_err() {
+                    return Some("File no longer exists".to_string());
+                }
+            }
+        }
+
+        if let (Some(cutoff), Some(last_used)) = (cutoff, entry.last_used()) {
+            if last_used < cutoff {
+                return Some(format!("Not used since {}", last_used.format("%Y-%m-%d")));
+            }
+        }
+
+        let subject = entry
+            .path
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| entry.href.clone());
+        patterns
+            .iter()
+            .find(|p| p.matches(&subject))
+            .map(|p| format!("Matches pattern {}", p.as_str()))
+    }
+}
+
+/// `$XDG_DATA_HOME/recently-used.xbel`, i.e. `~/.local/share/recently-used.xbel`.
+pub fn recently_used_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("recently-used.xbel"))
+}
+
+/// All entries of the user's recently-used.xbel, for callers that only need
+/// to know what has been opened.
+pub fn read_recent_entries() -> Vec<RecentEntry> {
+    recently_used_path()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .map(|content| parse_bookmarks(&content).into_iter().map(|b| b.entry).collect())
+        .unwrap_or_default()
+}
+
+fn parse_bookmarks(content: &str) -> Vec<BookmarkBlock> {
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = content[pos..].find("<bookmark") {
+        let tag_start = pos + offset;
+        let after_name = tag_start + "<bookmark".len();
+        // Skip <bookmark:applications> and friends
+        if !content[after_name..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/') {
+            pos = after_name;
+            continue;
+        }
+
+        let Some(tag_len) = content[tag_start..].find('>') else { break };
+        let tag_end = tag_start + tag_len + 1;
+        let open_tag = &content[tag_start..tag_end];
+
+        let element_end = if open_tag.ends_with("/>") {
+            tag_end
+        } else {
+            match content[tag_end..].find("</bookmark>") {
+                Some(close) => tag_end + close + "</bookmark>".len(),
+                None => break,
+            }
+        };
+
+        // Take the indentation before the element and the newline after it
+        let line_start = content[..tag_start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let start = if content[line_start..tag_start].trim().is_empty() { line_start } else { tag_start };
+        let end = if content[element_end..].starts_with('\n') { element_end + 1 } else { element_end };
+
+        let href = attribute(open_tag, "href").unwrap_or_default();
+        blocks.push(BookmarkBlock {
+            start,
+            end,
+            entry: RecentEntry {
+                path: file_uri_to_path(&href),
+                added: attribute(open_tag, "added").and_then(|v| parse_timestamp(&v)),
+                modified: attribute(open_tag, "modified").and_then(|v| parse_timestamp(&v)),
+                visited: attribute(open_tag, "visited").and_then(|v| parse_timestamp(&v)),
+                href,
+            },
+        });
+
+        pos = element_end;
+    }
+
+    blocks
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut search = 0;
+    while let Some(offset) = tag[search..].find(name) {
+        let start = search + offset;
+        search = start + name.len();
+        // Must be a whole attribute name, not the tail of another one
+        let preceded_by_space = tag[..start].ends_with(|c: char| c.is_whitespace());
+        let rest = tag[search..].trim_start();
+        if !preceded_by_space || !rest.starts_with('=') {
+            continue;
+        }
+        let rest = rest[1..].trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &rest[1..];
+        let close = value.find(quote)?;
+        return Some(unescape_xml(&value[..close]));
+    }
+    None
+}
+
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// GLib writes ISO 8601 with optional fractional seconds, always in UTC.
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // file://host/path is allowed; only the path part matters locally
+    let path = &rest[rest.find('/')?..];
+    Some(path_from_bytes(percent_decode(path)))
+}
+
+pub(crate) fn percent_decode(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+
+    decoded
+}
@@ -0,0 +1,309 @@
+// src-tauri/src/cleanup/reclaim.rs
+use super::trash_bin::trash_directories;
+use crate::paths::serde_path;
+use crate::system::mounts::{self, available_space};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How the files leave their directory.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Disposal {
+    Delete,
+    /// The trash and the quarantine live on the file's own filesystem, so
+    /// nothing is freed until they are emptied. The exception is a mount
+    /// without a trash of its own, from where files are copied into the
+    /// home trash.
+    Trash,
+    Quarantine,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PathReclaim {
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    #[serde(with = "serde_path")]
+    pub mount_point: PathBuf,
+    /// Apparent size of everything under the path.
+    pub logical_bytes: u64,
+    /// Allocated blocks that are released once the path is gone.
+    pub freed_bytes: u64,
+    /// Kept because other hard links outside the selection remain.
+    pub hard_linked_bytes: u64,
+    /// Kept until the processes that have the files open close them.
+    pub open_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MountReclaim {
+    #[serde(with = "serde_path")]
+    pub mount_point: PathBuf,
+    pub logical_bytes: u64,
+    pub estimated_freed: u64,
+    pub available_before: Option<u64>,
+    pub available_after: Option<u64>,
+    /// Change in available space; other writers and delayed frees (btrfs
+    /// commits, ZFS) make it differ from the estimate.
+    pub measured_freed: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReclaimReport {
+    pub paths: Vec<PathReclaim>,
+    pub mounts: Vec<MountReclaim>,
+}
+
+impl ReclaimReport {
+    pub fn logical_bytes(&self) -> u64 {
+        self.paths.iter().map(|p| p.logical_bytes).sum()
+    }
+
+    pub fn freed_bytes(&self) -> u64 {
+        self.paths.iter().map(|p| p.freed_bytes).sum()
+    }
+
+    /// Records free space on every mount before the files are removed.
+    pub fn measure_before(&mut self) {
+        for mount in &mut self.mounts {
+            mount.available_before = available_space(&mount.mount_point).ok();
+        }
+    }
+
+    /// Records free space afterwards and the difference.
+    pub fn measure_after(&mut self) {
+        for mount in &mut self.mounts {
+            mount.available_after = available_space(&mount.mount_point).ok();
+            mount.measured_freed = match (mount.available_before, mount.available_after) {
+                (Some(before), Some(after)) => Some(after as i64 - before as i64),
+                _ => None,
+            };
+        }
+    }
+
+    /// Keeps only the paths that were actually removed and recomputes the
+    /// per-mount estimates from them.
+    pub fn retain(&mut self, removed: &[PathBuf]) {
+        self.paths.retain(|p| removed.contains(&p.path));
+        for mount in &mut self.mounts {
+            let paths = self.paths.iter().filter(|p| p.mount_point == mount.mount_point);
+            let (logical, freed) = paths.fold((0, 0), |(l, f), p| (l + p.logical_bytes, f + p.freed_bytes));
+            mount.logical_bytes = logical;
+            mount.estimated_freed = freed;
+        }
+    }
+}
+
+/// Estimates what removing a set of paths really frees: allocated blocks
+/// rather than apparent sizes, nothing for quarantine or a trash on the same
+/// filesystem, and nothing for inodes that keep other hard links or are
+/// still held open.
+pub struct ReclaimEstimator;
+
+struct Inode {
+    links: u64,
+    seen: u64,
+    allocated: u64,
+    owners: Vec<usize>,
+}
+
+impl Inode {
+    /// The path the inode is charged to: the one that drops the last link.
+    fn owner(&self) -> usize {
+        *self.owners.last().unwrap_or(&0)
+    }
+}
+
+impl ReclaimEstimator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn estimate(&self, paths: &[PathBuf], disposal: Disposal) -> ReclaimReport {
+        let mut report = ReclaimReport::default();
+        let mut inodes: HashMap<(u64, u64), Inode> = HashMap::new();
+        let mut mount_points: HashMap<u64, PathBuf> = HashMap::new();
+        let mut frees = vec![disposal == Disposal::Delete; paths.len()];
+        let trash_devices: HashSet<u64> = match disposal {
+            Disposal::Trash => trash_directories()
+                .iter()
+                .filter_map(|(dir, _)| fs::metadata(dir).ok())
+                .map(|m| device(&m))
+                .collect(),
+            _ => HashSet::new(),
+        };
+
+        for (index, path) in paths.iter().enumerate() {
+            let mut entry = PathReclaim {
+                path: path.clone(),
+                ..Default::default()
+            };
+            for item in walkdir::WalkDir::new(path).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+                let Ok(metadata) = item.metadata() else { continue };
+                if item.depth() == 0 {
+                    entry.mount_point = mount_points
+                        .entry(device(&metadata))
+                        .or_insert_with(|| mounts::mount_for(path).map(|m| m.mount_point).unwrap_or_default())
+                        .clone();
+                    if disposal == Disposal::Trash {
+                        frees[index] = trash_leaves_device(device(&metadata), &entry.mount_point, &trash_devices);
+                    }
+                }
+                if metadata.is_dir() {
+                    continue;
+                }
+                entry.logical_bytes += metadata.len();
+
+                let inode = inodes.entry((device(&metadata), inode(&metadata))).or_insert(Inode {
+                    links: links(&metadata),
+                    seen: 0,
+                    allocated: allocated(&metadata),
+                    owners: Vec::new(),
+                });
+                inode.seen += 1;
+                inode.owners.push(index);
+            }
+            report.paths.push(entry);
+        }
+
+        // Only inodes that would otherwise be freed are looked for in /proc
+        let candidates: HashSet<(u64, u64)> = inodes
+            .iter()
+            .filter(|(_, inode)| frees[inode.owner()] && inode.seen >= inode.links && inode.allocated > 0)
+            .map(|(key, _)| *key)
+            .collect();
+        let open_files = open_files(&candidates);
+
+        for (key, inode) in &inodes {
+            let owner = inode.owner();
+            if frees[owner] {
+                let entry = &mut report.paths[owner];
+                if inode.seen < inode.links {
+                    entry.hard_linked_bytes += inode.allocated;
+                } else if open_files.contains(key) {
+                    entry.open_bytes += inode.allocated;
+                } else {
+                    entry.freed_bytes += inode.allocated;
+                }
+            }
+        }
+
+        let mut by_mount: HashMap<PathBuf, MountReclaim> = HashMap::new();
+        for entry in &report.paths {
+            let mount = by_mount.entry(entry.mount_point.clone()).or_insert_with(|| MountReclaim {
+                mount_point: entry.mount_point.clone(),
+                ..Default::default()
+            });
+            mount.logical_bytes += entry.logical_bytes;
+            mount.estimated_freed += entry.freed_bytes;
+        }
+        report.mounts = by_mount.into_values().collect();
+        report
+    }
+}
+
+impl Default for ReclaimEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether trashing from `device` moves files off it: none of our trash
+/// directories is on it and its top directory is not writable, so no
+/// `.Trash-$uid` can be created there and the home trash takes them.
+fn trash_leaves_device(device: u64, mount_point: &Path, trash_devices: &HashSet<u64>) -> bool {
+    if trash_devices.contains(&device) {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let Ok(top) = std::ffi::CString::new(mount_point.as_os_str().as_bytes()) else { return false };
+        unsafe { libc::access(top.as_ptr(), libc::W_OK) != 0 }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = mount_point;
+        false
+    }
+}
+
+/// Which of `candidates` (device and inode) some process has open, from
+/// `/proc/*/fd`. Processes of other users are not visible without root.
+#[cfg(target_os = "linux")]
+fn open_files(candidates: &HashSet<(u64, u64)>) -> HashSet<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let mut open = HashSet::new();
+    if candidates.is_empty() {
+        return open;
+    }
+    let Ok(processes) = fs::read_dir("/proc") else { return open };
+    for process in processes.filter_map(|e| e.ok()) {
+        if !process.file_name().to_string_lossy().bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+        let Ok(descriptors) = fs::read_dir(process.path().join("fd")) else { continue };
+        for descriptor in descriptors.filter_map(|e| e.ok()) {
+            if let Ok(metadata) = fs::metadata(descriptor.path()) {
+                let key = (metadata.dev(), metadata.ino());
+                if metadata.is_file() && candidates.contains(&key) {
+                    open.insert(key);
+                    if open.len() == candidates.len() {
+                        return open;
+                    }
+                }
+            }
+        }
+    }
+    open
+}
+
+#[cfg(not(target_os = "linux"))]
+fn open_files(_candidates: &HashSet<(u64, u64)>) -> HashSet<(u64, u64)> {
+    HashSet::new()
+}
+
+#[cfg(unix)]
+fn device(metadata: &fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::dev(metadata)
+}
+
+#[cfg(unix)]
+fn inode(metadata: &fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::ino(metadata)
+}
+
+#[cfg(unix)]
+fn links(metadata: &fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::nlink(metadata)
+}
+
+/// Sparse and compressed files take fewer blocks than their length.
+#[cfg(unix)]
+fn allocated(metadata: &fs::Metadata) -> u64 {
+    std::os::unix::fs::MetadataExt::blocks(metadata) * 512
+}
+
+#[cfg(not(unix))]
+fn device(_metadata: &fs::Metadata) -> u64 {
+    0
+}
+
+/// Without inode numbers every file counts as its own inode.
+#[cfg(not(unix))]
+fn inode(_metadata: &fs::Metadata) -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+#[cfg(not(unix))]
+fn links(_metadata: &fs::Metadata) -> u64 {
+    1
+}
+
+#[cfg(not(unix))]
+fn allocated(metadata: &fs::Metadata) -> u64 {
+    metadata.len()
+}
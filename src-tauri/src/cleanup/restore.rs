@@ -1,71 +1,109 @@
 // src-tauri/src/cleanup/restore.rs
-use crate::error::{CleanerError, Result};
+use super::journal::Journal;
+use crate::error::Result;
+use crate::paths::serde_path;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+/// A journaled file that still has a backup in the backup store.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RestorePoint {
     pub id: i64,
+    pub operation_id: i64,
     pub timestamp: String,
+    #[serde(with = "serde_path")]
     pub original_path: PathBuf,
-    pub backup_path: PathBuf,
+    pub file_hash: String,
     pub file_size: u64,
     pub restored: bool,
 }
 
-pub fn get_restore_points(conn: &Connection) -> Result<Vec<RestorePoint>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, timestamp, original_path, backup_path, file_size, restored 
-         FROM restore_points 
-         WHERE restored = 0 
-         ORDER BY timestamp DESC"
-    )?;
-
-    let points = stmt.query_map([], |row| {
-        Ok(RestorePoint {
-            id: row.get(0)?,
-            timestamp: row.get(1)?,
-            original_path: PathBuf::from(row.get::<_, String>(2)?),
-            backup_path: PathBuf::from(row.get::<_, String>(3)?),
-            file_size: row.get::<_, i64>(4)? as u64,
-            restored: row.get::<_, i64>(5)? != 0,
-        })
-    })?
-    .filter_map(|r| r.ok())
-    .collect();
-
-    Ok(points)
+/// What to do when something already exists where a file is restored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub enum ConflictPolicy {
+    /// Restore next to it as `name (restored N).ext`.
+    #[default]
+    KeepBoth,
+    /// Replace it. The existing file is only removed once the restore succeeded.
+    Overwrite,
+    /// Leave it and do not restore.
+    Skip,
+    /// Restore into this directory instead of the original location,
+    /// keeping both on a name clash there.
+    RestoreTo(#[serde(with = "serde_path")] PathBuf),
 }
 
-pub fn restore_file(conn: &Connection, restore_point_id: i64) -> Result<PathBuf> {
-    let mut stmt = conn.prepare(
-        "SELECT original_path, backup_path FROM restore_points WHERE id = ?"
-    )?;
-
-    let (original_path, backup_path): (String, String) = stmt
-        .query_row([restore_point_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RestoreOutcome {
+    Restored,
+    /// Restored under a different name because the path was taken.
+    Renamed,
+    Overwritten,
+    Skipped,
+}
 
-    let original = PathBuf::from(&original_path);
-    let backup = PathBuf::from(&backup_path);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredFile {
+    pub id: i64,
+    #[serde(with = "serde_path")]
+    pub original_path: PathBuf,
+    /// `None` when skipped.
+    #[serde(with = "serde_path::option")]
+    pub restored_to: Option<PathBuf>,
+    pub outcome: RestoreOutcome,
+}
 
-    if !backup.exists() {
-        return Err(CleanerError::FileNotFound(backup_path));
-    }
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestoreReport {
+    pub files: Vec<RestoredFile>,
+    #[serde(with = "serde_path::failures")]
+    pub failed: Vec<(PathBuf, String)>,
+}
 
-    if let Some(parent) = original.parent() {
-        fs::create_dir_all(parent)?;
-    }
+pub fn get_restore_points(conn: &Connection, app_data_dir: &Path) -> Result<Vec<RestorePoint>> {
+    let points = Journal::new(conn, app_data_dir)
+        .restorable_files()?
+        .into_iter()
+        .filter_map(|file| {
+            Some(RestorePoint {
+                id: file.id,
+                operation_id: file.operation_id,
+                timestamp: file.processed_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                original_path: file.original_path,
+                file_hash: file.backup_hash?,
+                file_size: file.size,
+                restored: false,
+            })
+        })
+        .collect();
 
-    fs::copy(&backup, &original)?;
+    Ok(points)
+}
 
-    conn.execute(
-        "UPDATE restore_points SET restored = 1 WHERE id = ?",
-        [restore_point_id],
-    )?;
+pub fn restore_file(
+    conn: &Connection,
+    app_data_dir: &Path,
+    restore_point_id: i64,
+    policy: &ConflictPolicy,
+) -> Result<RestoredFile> {
+    Journal::new(conn, app_data_dir).restore_file(restore_point_id, policy)
+}
 
-    let _ = fs::remove_file(&backup);
+pub fn restore_files(
+    conn: &Connection,
+    app_data_dir: &Path,
+    restore_point_ids: &[i64],
+    policy: &ConflictPolicy,
+) -> Result<RestoreReport> {
+    Journal::new(conn, app_data_dir).restore_files(restore_point_ids, policy)
+}
 
-    Ok(original)
+pub fn restore_operation(
+    conn: &Connection,
+    app_data_dir: &Path,
+    operation_id: i64,
+    policy: &ConflictPolicy,
+) -> Result<RestoreReport> {
+    Journal::new(conn, app_data_dir).restore_operation(operation_id, policy)
 }
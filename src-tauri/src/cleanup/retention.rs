@@ -0,0 +1,202 @@
+// src-tauri/src/cleanup/retention.rs
+use super::backup_store::BackupStore;
+use super::journal::Journal;
+use crate::config::BackupRetention;
+use crate::error::Result;
+use crate::paths::serde_path;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupUsage {
+    pub restore_points: u64,
+    pub objects: u64,
+    /// Size of the backed-up files as they were on disk.
+    pub logical_bytes: u64,
+    /// Space the store takes after dedup and compression.
+    pub stored_bytes: u64,
+    pub oldest: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExpiryReason {
+    Age,
+    Count,
+    Size,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpiredRestorePoint {
+    pub id: i64,
+    #[serde(with = "serde_path")]
+    pub original_path: PathBuf,
+    pub created_at: Option<DateTime<Utc>>,
+    pub reason: ExpiryReason,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupGcResult {
+    pub dry_run: bool,
+    pub expired: Vec<ExpiredRestorePoint>,
+    /// Store bytes that are (or would be) released; shared objects only
+    /// count once the last restore point using them is gone.
+    pub freed_bytes: u64,
+    pub usage: BackupUsage,
+}
+
+pub fn backup_usage(conn: &Connection, app_data_dir: &Path) -> Result<BackupUsage> {
+    let points = Journal::new(conn, app_data_dir).restorable_files()?;
+    let (objects, stored_bytes): (i64, i64) = conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(stored_size), 0) FROM backup_objects",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let logical_bytes: i64 = conn.query_row("SELECT COALESCE(SUM(size), 0) FROM backup_entries", [], |row| row.get(0))?;
+
+    Ok(BackupUsage {
+        restore_points: points.len() as u64,
+        objects: objects as u64,
+        logical_bytes: logical_bytes as u64,
+        stored_bytes: stored_bytes as u64,
+        oldest: points.iter().filter_map(|p| p.processed_at).min(),
+    })
+}
+
+/// Expires restore points that fall outside the retention limits and
+/// deletes the backup objects nothing refers to any more. Expired journal
+/// entries stay in the journal, marked `Expired`.
+pub struct BackupGc<'a> {
+    conn: &'a Connection,
+    app_data_dir: PathBuf,
+    retention: BackupRetention,
+}
+
+impl<'a> BackupGc<'a> {
+    pub fn new(conn: &'a Connection, app_data_dir: &Path, retention: BackupRetention) -> Self {
+        Self {
+            conn,
+            app_data_dir: app_data_dir.to_path_buf(),
+            retention,
+        }
+    }
+
+    pub fn run(&self, dry_run: bool) -> Result<BackupGcResult> {
+        let journal = Journal::new(self.conn, &self.app_data_dir);
+        // Newest first
+        let points = journal.restorable_files()?;
+        let (mut references, object_sizes) = self.object_references()?;
+        // Objects an interrupted backup left without a manifest go regardless
+        let orphaned: u64 = object_sizes
+            .iter()
+            .filter(|(hash, _)| !references.contains_key(*hash))
+            .map(|(_, size)| size)
+            .sum();
+        let mut stored_bytes: u64 = object_sizes.values().sum::<u64>() - orphaned;
+
+        let cutoff = self.retention.max_age_days.map(|days| Utc::now() - Duration::days(days as i64));
+        let max_count = self.retention.max_restore_points.map(|n| n as usize);
+        let mut expired = Vec::new();
+        let mut freed_bytes = orphaned;
+
+        let release = |file_id: i64, references: &mut HashMap<String, usize>| {
+            let mut freed = 0;
+            for hash in self.objects_of(file_id).unwrap_or_default() {
+                if let Some(count) = references.get_mut(&hash) {
+                    *count -= 1;
+                    if *count == 0 {
+                        freed += object_sizes.get(&hash).copied().unwrap_or(0);
+                    }
+                }
+            }
+            freed
+        };
+
+        // Age and count first, oldest to newest so the size check below
+        // sees what is left
+        let mut kept = Vec::new();
+        for (index, point) in points.iter().enumerate().rev() {
+            let reason = if max_count.map(|max| index >= max).unwrap_or(false) {
+                Some(ExpiryReason::Count)
+            } else if matches!((cutoff, point.processed_at), (Some(c), Some(t)) if t < c) {
+                Some(ExpiryReason::Age)
+            } else {
+                None
+            };
+            match reason {
+                Some(reason) => {
+                    let freed = release(point.id, &mut references);
+                    freed_bytes += freed;
+                    stored_bytes = stored_bytes.saturating_sub(freed);
+                    expired.push((point, reason));
+                }
+                None => kept.push(point),
+            }
+        }
+
+        if let Some(max_bytes) = self.retention.max_total_bytes {
+            for point in kept {
+                if stored_bytes <= max_bytes {
+                    break;
+                }
+                let freed = release(point.id, &mut references);
+                freed_bytes += freed;
+                stored_bytes = stored_bytes.saturating_sub(freed);
+                expired.push((point, ExpiryReason::Size));
+            }
+        }
+
+        if !dry_run {
+            for (point, _) in &expired {
+                journal.expire_backup(point.id)?;
+            }
+            freed_bytes = BackupStore::new(&self.app_data_dir).remove_unreferenced(self.conn)?;
+        }
+
+        Ok(BackupGcResult {
+            dry_run,
+            expired: expired
+                .into_iter()
+                .map(|(point, reason)| ExpiredRestorePoint {
+                    id: point.id,
+                    original_path: point.original_path.clone(),
+                    created_at: point.processed_at,
+                    reason,
+                })
+                .collect(),
+            freed_bytes,
+            usage: backup_usage(self.conn, &self.app_data_dir)?,
+        })
+    }
+
+    /// How many manifest entries use each object, and each object's size on disk.
+    fn object_references(&self) -> Result<(HashMap<String, usize>, HashMap<String, u64>)> {
+        let mut references = HashMap::new();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT object_hash FROM backup_entries WHERE object_hash IS NOT NULL")?;
+        for hash in stmt.query_map([], |row| row.get::<_, String>(0))?.filter_map(|r| r.ok()) {
+            *references.entry(hash).or_insert(0) += 1;
+        }
+
+        let mut stmt = self.conn.prepare("SELECT hash, stored_size FROM backup_objects")?;
+        let sizes = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok((references, sizes))
+    }
+
+    fn objects_of(&self, file_id: i64) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT object_hash FROM backup_entries WHERE journal_file_id = ?1 AND object_hash IS NOT NULL")?;
+        let hashes = stmt
+            .query_map([file_id], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(hashes)
+    }
+}
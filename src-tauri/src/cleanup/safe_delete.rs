@@ -1,10 +1,18 @@
 // src-tauri/src/cleanup/safe_delete.rs
-use crate::database;
-use crate::error::{Result};
+use super::executor::{CleanupExecutor, CleanupProgress};
+use super::journal::{Journal, JournalAction};
+use super::secure_delete::{wipe_limitations, SecureDeleteOptions};
+use crate::error::{CleanerError, Result};
+use crate::paths::serde_path;
+use super::history::{add_cleanup_history, CleanedFile};
+use super::reclaim::{Disposal, MountReclaim, ReclaimEstimator};
+use crate::scanner::{BrowserScanner, CleanupRule, FileIdentity, RuleEngine};
+use crate::state::AppState;
 use rusqlite::Connection;
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeleteOptions {
@@ -12,6 +20,18 @@ pub struct DeleteOptions {
     pub use_trash: bool,
     pub create_restore_point: bool,
     pub secure_delete: bool,
+    /// Move files into the quarantine instead; see `Quarantine`.
+    #[serde(default)]
+    pub quarantine: bool,
+    #[serde(default)]
+    pub secure_delete_options: SecureDeleteOptions,
+    /// Files removed at the same time on one filesystem.
+    #[serde(default = "default_workers_per_device")]
+    pub workers_per_device: usize,
+}
+
+fn default_workers_per_device() -> usize {
+    4
 }
 
 impl Default for DeleteOptions {
@@ -21,131 +41,199 @@ impl Default for DeleteOptions {
             use_trash: true,
             create_restore_point: true,
             secure_delete: false,
+            quarantine: false,
+            secure_delete_options: SecureDeleteOptions::default(),
+            workers_per_device: default_workers_per_device(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeleteResult {
+    #[serde(with = "serde_path::vec")]
     pub deleted_files: Vec<PathBuf>,
+    #[serde(with = "serde_path::failures")]
     pub failed_files: Vec<(PathBuf, String)>,
+    /// Apparent size of everything removed from its directory.
+    pub logical_bytes: u64,
+    /// Space actually released: nothing for the trash or quarantine, and
+    /// nothing for files that keep other hard links or are held open.
     pub bytes_freed: u64,
-    pub restore_point_id: Option<i64>,
+    /// Per-mount estimate and, after a real run, free space measured before
+    /// and after.
+    pub mounts: Vec<MountReclaim>,
+    /// Journal operation that recorded the deletion; `restore_operation`
+    /// brings back all of its backed-up files.
+    pub operation_id: Option<i64>,
+    /// Why a secure delete may have left data recoverable (CoW filesystem,
+    /// SSD, shared extents, hard links).
+    pub warnings: Vec<String>,
     pub was_dry_run: bool,
 }
 
 pub struct SafeDeleter {
     options: DeleteOptions,
-    backup_dir: PathBuf,
+    app_data_dir: PathBuf,
+    identities: HashMap<PathBuf, FileIdentity>,
 }
 
 impl SafeDeleter {
     pub fn new(options: DeleteOptions, app_data_dir: &Path) -> Self {
-        let backup_dir = app_data_dir.join("backups");
-        Self { options, backup_dir }
+        Self {
+            options,
+            app_data_dir: app_data_dir.to_path_buf(),
+            identities: HashMap::new(),
+        }
     }
 
+    /// Device and inode each path had when it was scanned. A path that now
+    /// refers to a different file is not touched.
+    pub fn with_identities(mut self, identities: HashMap<PathBuf, FileIdentity>) -> Self {
+        self.identities = identities;
+        self
+    }
+
+    /// Blocks until every file is handled; run it off the async runtime.
+    /// Cancelling through `state` stops before the next file and between
+    /// batches of a large directory.
     pub fn delete_files(
         &self,
         files: &[PathBuf],
         db_conn: &Connection,
+        state: Option<Arc<AppState>>,
     ) -> Result<DeleteResult> {
         let mut deleted = Vec::new();
         let mut failed = Vec::new();
-        let mut bytes_freed = 0u64;
-        let mut restore_point_id = None;
+        let mut operation_id = None;
+        let mut warnings = Vec::new();
 
-        if self.options.create_restore_point && !self.options.dry_run {
-            fs::create_dir_all(&self.backup_dir)?;
-        }
+        let browser_profiles = BrowserScanner::discover_profiles();
+        let mut planned = Vec::new();
 
         for path in files {
-            if !path.exists() {
+            // Not `exists()`: that follows symlinks
+            let Ok(metadata) = std::fs::symlink_metadata(path) else {
                 failed.push((path.clone(), "File not found".to_string()));
                 continue;
+            };
+            if let Some(expected) = self.identities.get(path) {
+                if FileIdentity::of(&metadata).as_ref() != Some(expected) {
+                    failed.push((path.clone(), CleanerError::Changed(path.display().to_string()).to_string()));
+                    continue;
+                }
             }
 
-            let file_size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
-
-            if self.options.dry_run {
-                deleted.push(path.clone());
-                bytes_freed += file_size;
+            if let Some(browser) = BrowserScanner::running_browser_for(path, &browser_profiles) {
+                failed.push((path.clone(), format!("{:?} is running; close it before cleaning", browser)));
                 continue;
             }
 
-            if self.options.create_restore_point {
-                match self.backup_file(path, db_conn) {
-                    Ok(id) => restore_point_id = Some(id),
-                    Err(e) => {
-                        tracing::warn!("Failed to create backup for {:?}: {}", path, e);
-                    }
-                }
-            }
+            planned.push(path.clone());
+        }
 
-            let delete_result = if self.options.use_trash {
-                trash::delete(path).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))
+        let disposal = if self.options.quarantine {
+            Disposal::Quarantine
+        } else if self.options.use_trash {
+            Disposal::Trash
+        } else {
+            Disposal::Delete
+        };
+        let mut reclaim = ReclaimEstimator::new().estimate(&planned, disposal);
+
+        if self.options.dry_run {
+            deleted = planned;
+        } else if !planned.is_empty() {
+            let (kind, action) = if self.options.quarantine {
+                ("quarantine", JournalAction::Quarantine)
+            } else if self.options.use_trash {
+                ("trash", JournalAction::Trash)
             } else if self.options.secure_delete {
-                super::secure_delete::secure_delete(path)
+                ("secure_delete", JournalAction::SecureDelete)
             } else {
-                if path.is_dir() {
-                    fs::remove_dir_all(path)
-                } else {
-                    fs::remove_file(path)
-                }
+                ("delete", JournalAction::Delete)
             };
-
-            match delete_result {
-                Ok(_) => {
-                    deleted.push(path.clone());
-                    bytes_freed += file_size;
+            // A backup would defeat the point of a secure delete, and a
+            // quarantined file is its own restore point
+            let with_backup = self.options.create_restore_point
+                && !matches!(action, JournalAction::SecureDelete | JournalAction::Quarantine);
+
+            if action == JournalAction::SecureDelete {
+                for path in &planned {
+                    for limitation in wipe_limitations(path) {
+                        let warning = limitation.to_string();
+                        if !warnings.contains(&warning) {
+                            tracing::warn!("Secure delete: {}", warning);
+                            warnings.push(warning);
+                        }
+                    }
                 }
-                Err(e) => {
-                    failed.push((path.clone(), e.to_string()));
+            }
+
+            let journal = Journal::new(db_conn, &self.app_data_dir);
+            let entries: Vec<_> = planned.iter().map(|p| (p.clone(), action, with_backup)).collect();
+            let id = journal.begin(kind, None, &entries)?;
+            let files = journal.files(id)?;
+            // Classified up front; the rules need the file's metadata
+            let rules = Arc::new(RuleEngine::new(CleanupRule::default_rules()));
+            let mut records: HashMap<i64, CleanedFile> = files
+                .iter()
+                .map(|f| (f.id, CleanedFile::classify(&rules, &f.original_path, f.size)))
+                .collect();
+            let mut cleaned = Vec::new();
+
+            let progress = Arc::new(CleanupProgress::new(
+                state,
+                files.len() as u64,
+                files.iter().map(|f| f.size).sum(),
+            ));
+            let executor = CleanupExecutor::new(
+                &self.app_data_dir,
+                self.options.secure_delete_options.clone(),
+                self.options.workers_per_device,
+            )
+            .with_rules(rules);
+            reclaim.measure_before();
+
+            for (file, outcome) in executor.run(db_conn, files, &progress)? {
+                match outcome {
+                    Some(Ok(())) => {
+                        cleaned.extend(records.remove(&file.id));
+                        deleted.push(file.original_path);
+                    }
+                    Some(Err(e)) => failed.push((file.original_path, e.to_string())),
+                    None => {
+                        let reason = CleanerError::Cancelled.to_string();
+                        journal.skip(&file, &reason)?;
+                        failed.push((file.original_path, reason));
+                    }
                 }
             }
-        }
 
-        if !self.options.dry_run && !deleted.is_empty() {
-            let details = serde_json::to_string(&deleted).ok();
-            database::add_cleanup_history(
-                db_conn,
-                "delete",
-                deleted.len() as i64,
-                bytes_freed as i64,
-                details.as_deref(),
-            )?;
+            journal.commit(id)?;
+            operation_id = Some(id);
+            reclaim.measure_after();
+            reclaim.retain(&deleted);
+
+            if !cleaned.is_empty() {
+                add_cleanup_history(db_conn, kind, operation_id, &cleaned)?;
+            }
         }
 
         Ok(DeleteResult {
             deleted_files: deleted,
             failed_files: failed,
-            bytes_freed,
-            restore_point_id,
+            logical_bytes: reclaim.logical_bytes(),
+            bytes_freed: reclaim.freed_bytes(),
+            mounts: reclaim.mounts,
+            operation_id,
+            warnings,
             was_dry_run: self.options.dry_run,
         })
     }
+}
 
-    fn backup_file(&self, path: &Path, db_conn: &Connection) -> Result<i64> {
-        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
-        let file_name = path.file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
-        
-        let backup_name = format!("{}_{}", timestamp, file_name);
-        let backup_path = self.backup_dir.join(&backup_name);
-
-        fs::copy(path, &backup_path)?;
-
-        let file_size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
-        
-        let id = database::add_restore_point(
-            db_conn,
-            &path.to_string_lossy(),
-            &backup_path.to_string_lossy(),
-            None,
-            file_size as i64,
-        )?;
-
-        Ok(id)
-    }
+/// Backs `path` up through the journal before it is changed in place and
+/// returns the restore point id.
+pub fn create_restore_point(app_data_dir: &Path, path: &Path, db_conn: &Connection) -> Result<i64> {
+    Journal::new(db_conn, app_data_dir).record_modification("modify", path)
 }
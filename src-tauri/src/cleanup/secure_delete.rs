@@ -1,82 +1,455 @@
 // src-tauri/src/cleanup/secure_delete.rs
-use std::fs::{self, File, OpenOptions};
+use super::executor::CleanupProgress;
+use crate::error::Result;
+use crate::scanner::FileIdentity;
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, File};
 use std::io::{Seek, SeekFrom, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub fn secure_delete(path: &Path) -> std::io::Result<()> {
-    if path.is_dir() {
-        for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            secure_delete(&entry.path())?;
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Filesystems that write changed blocks to new locations, leaving the old
+/// contents on disk until the space is reused.
+#[cfg(target_os = "linux")]
+const COPY_ON_WRITE_FILESYSTEMS: &[&str] = &["btrfs", "zfs", "bcachefs"];
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum WipeScheme {
+    /// A single pass of zeros.
+    Zeros,
+    /// `passes` passes of random data.
+    #[default]
+    Random,
+    /// DoD 5220.22-M: zeros, ones, then random data.
+    Dod3Pass,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SecureDeleteOptions {
+    pub scheme: WipeScheme,
+    /// Passes for `WipeScheme::Random`; the other schemes have a fixed count.
+    pub passes: u32,
+    /// Rename files to random names before unlinking so the directory entry
+    /// does not keep the original name.
+    pub obfuscate_names: bool,
+}
+
+impl Default for SecureDeleteOptions {
+    fn default() -> Self {
+        Self {
+            scheme: WipeScheme::Random,
+            passes: 3,
+            obfuscate_names: true,
         }
-        fs::remove_dir(path)?;
-    } else {
-        let metadata = fs::metadata(path)?;
-        let size = metadata.len() as usize;
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Pass {
+    Fill(u8),
+    Random,
+}
+
+impl SecureDeleteOptions {
+    fn passes(&self) -> Vec<Pass> {
+        match self.scheme {
+            WipeScheme::Zeros => vec![Pass::Fill(0x00)],
+            WipeScheme::Random => vec![Pass::Random; self.passes.max(1) as usize],
+            WipeScheme::Dod3Pass => vec![Pass::Fill(0x00), Pass::Fill(0xFF), Pass::Random],
+        }
+    }
+}
+
+/// Why overwriting a file may not destroy its contents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum WipeLimitation {
+    /// Overwrites land in new blocks; the old ones survive until reused,
+    /// and snapshots keep them indefinitely.
+    CopyOnWrite { file_system: String, mount_point: PathBuf },
+    /// The file shares extents with another file (reflink copy or
+    /// snapshot), so its blocks are not overwritten in place.
+    SharedExtents { path: PathBuf },
+    /// Wear leveling redirects writes to fresh flash cells; TRIM may or
+    /// may not erase the old ones.
+    SolidState { device: String, trim: bool },
+    /// Other names point at the same data; it is unlinked but not
+    /// overwritten, so they keep working.
+    HardLinks { path: PathBuf, links: u64 },
+}
+
+impl fmt::Display for WipeLimitation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WipeLimitation::CopyOnWrite { file_system, mount_point } => write!(
+                f,
+                "{} is a copy-on-write filesystem ({}); overwriting does not guarantee the old data is gone",
+                mount_point.display(),
+                file_system
+            ),
+            WipeLimitation::SharedExtents { path } => write!(
+                f,
+                "{} shares data with a reflinked copy or snapshot; overwriting does not reach the shared blocks",
+                path.display()
+            ),
+            WipeLimitation::SolidState { device, trim } => write!(
+                f,
+                "{} is a solid-state drive{}; wear leveling means overwriting gives no guarantee",
+                device,
+                if *trim { " with TRIM" } else { "" }
+            ),
+            WipeLimitation::HardLinks { path, links } => write!(
+                f,
+                "{} has {} other hard link(s); it was unlinked without overwriting the shared data",
+                path.display(),
+                links - 1
+            ),
+        }
+    }
+}
+
+/// Overwrites a file (or every file under a directory) according to
+/// `options`, then truncates, renames and unlinks it. The tree is walked
+/// like `remove_tree`: relative to opened directories, only if `path` still
+/// has the `expected` device and inode, never following symlinks or
+/// entering another mount, and cancellable through `progress`.
+pub fn secure_delete(
+    path: &Path,
+    expected: Option<FileIdentity>,
+    options: &SecureDeleteOptions,
+    progress: Option<&CleanupProgress>,
+) -> Result<()> {
+    let rng = StdRng::from_entropy();
+    #[cfg(unix)]
+    {
+        let mut wiper = unix::Wiper { options, rng };
+        super::unlink::remove_tree_with(path, expected, progress, &mut wiper)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (expected, progress);
+        let mut rng = rng;
+        wipe_path(path, options, &mut rng)
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use super::{overwrite, random_name, SecureDeleteOptions};
+    use crate::cleanup::unlink::UnlinkHook;
+    use crate::error::{CleanerError, Result};
+    use rand::rngs::StdRng;
+    use std::ffi::{CStr, CString};
+    use std::fs::File;
+    use std::io;
+    use std::os::fd::{FromRawFd, OwnedFd};
+    use std::os::unix::fs::MetadataExt;
+
+    pub(super) struct Wiper<'a> {
+        pub(super) options: &'a SecureDeleteOptions,
+        pub(super) rng: StdRng,
+    }
+
+    impl UnlinkHook for Wiper<'_> {
+        fn before_unlink(&mut self, dir: libc::c_int, name: &CStr, stat: &libc::stat) -> Result<CString> {
+            let is_file = stat.st_mode & libc::S_IFMT == libc::S_IFREG;
+            // Data shared with other hard links is unlinked, not overwritten
+            if is_file && stat.st_size > 0 && stat.st_nlink <= 1 {
+                let fd = unsafe { libc::openat(dir, name.as_ptr(), libc::O_WRONLY | libc::O_NOFOLLOW | libc::O_CLOEXEC) };
+                if fd < 0 {
+                    return Err(io::Error::last_os_error().into());
+                }
+                let mut file = File::from(unsafe { OwnedFd::from_raw_fd(fd) });
+                let metadata = file.metadata()?;
+                // Swapped for another file since the lookup
+                if (metadata.dev(), metadata.ino()) != (stat.st_dev as u64, stat.st_ino as u64) {
+                    return Err(CleanerError::Changed(String::from_utf8_lossy(name.to_bytes()).into_owned()));
+                }
+                for pass in self.options.passes() {
+                    overwrite(&mut file, metadata.len(), pass, &mut self.rng)?;
+                }
+                file.set_len(0)?;
+                file.sync_all()?;
+            }
+            self.obfuscate_name(dir, name)
+        }
+    }
+
+    impl Wiper<'_> {
+        /// Renames to a random name of the same length, then to a
+        /// one-character name, within `dir`, and returns the final name.
+        fn obfuscate_name(&mut self, dir: libc::c_int, name: &CStr) -> Result<CString> {
+            let mut current = name.to_owned();
+            if !self.options.obfuscate_names {
+                return Ok(current);
+            }
+            let length = name.to_bytes().len().max(1);
 
-        if size > 0 {
-            let mut file = OpenOptions::new()
-                .write(true)
-                .open(path)?;
+            for length in [length, 1] {
+                let candidate = (0..16)
+                    .map(|_| CString::new(random_name(&mut self.rng, length)).expect("random names have no NUL"))
+                    .find(|candidate| !exists_at(dir, candidate));
+                if let Some(candidate) = candidate {
+                    if unsafe { libc::renameat(dir, current.as_ptr(), dir, candidate.as_ptr()) } != 0 {
+                        return Err(io::Error::last_os_error().into());
+                    }
+                    current = candidate;
+                }
+            }
 
-            overwrite_file(&mut file, size, 0x00)?;
-            overwrite_file(&mut file, size, 0xFF)?;
-            overwrite_with_random(&mut file, size)?;
+            // Make the renames durable before the unlink
+            unsafe { libc::fsync(dir) };
+            Ok(current)
+        }
+    }
+
+    fn exists_at(dir: libc::c_int, name: &CStr) -> bool {
+        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+        unsafe { libc::fstatat(dir, name.as_ptr(), &mut stat, libc::AT_SYMLINK_NOFOLLOW) == 0 }
+    }
+}
 
-            file.sync_all()?;
+#[cfg(not(unix))]
+fn wipe_path(path: &Path, options: &SecureDeleteOptions, rng: &mut StdRng) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            wipe_path(&entry?.path(), options, rng)?;
         }
+        let path = obfuscate_name(path, options, rng)?;
+        fs::remove_dir(path)?;
+        return Ok(());
+    }
 
-        fs::remove_file(path)?;
+    if metadata.is_file() && metadata.len() > 0 {
+        let mut file = fs::OpenOptions::new().write(true).open(path)?;
+        for pass in options.passes() {
+            overwrite(&mut file, metadata.len(), pass, rng)?;
+        }
+        file.set_len(0)?;
+        file.sync_all()?;
     }
 
+    let path = obfuscate_name(path, options, rng)?;
+    fs::remove_file(path)?;
     Ok(())
 }
 
-fn overwrite_file(file: &mut File, size: usize, byte: u8) -> std::io::Result<()> {
+fn overwrite(file: &mut File, size: u64, pass: Pass, rng: &mut StdRng) -> Result<()> {
     file.seek(SeekFrom::Start(0))?;
-    
-    let buffer = vec![byte; 65536.min(size)];
+    let mut buffer = match pass {
+        Pass::Fill(byte) => vec![byte; BUFFER_SIZE],
+        Pass::Random => vec![0u8; BUFFER_SIZE],
+    };
+
     let mut remaining = size;
-    
     while remaining > 0 {
-        let to_write = remaining.min(buffer.len());
-        file.write_all(&buffer[..to_write])?;
-        remaining -= to_write;
+        let chunk = remaining.min(BUFFER_SIZE as u64) as usize;
+        if let Pass::Random = pass {
+            rng.fill_bytes(&mut buffer[..chunk]);
+        }
+        file.write_all(&buffer[..chunk])?;
+        remaining -= chunk as u64;
     }
-    
+
+    // Each pass has to reach the disk, not just the page cache
     file.sync_all()?;
     Ok(())
 }
 
-fn overwrite_with_random(file: &mut File, size: usize) -> std::io::Result<()> {
-    use std::collections::hash_map::RandomState;
-    use std::hash::{BuildHasher, Hasher};
-    
-    file.seek(SeekFrom::Start(0))?;
-    
-    let mut buffer = vec![0u8; 65536.min(size)];
-    let hasher_builder = RandomState::new();
-    let mut remaining = size;
-    let mut seed: u64 = 0;
-    
-    while remaining > 0 {
-        for chunk in buffer.chunks_mut(8) {
-            let mut hasher = hasher_builder.build_hasher();
-            hasher.write_u64(seed);
-            seed = hasher.finish();
-            let bytes = seed.to_le_bytes();
-            for (i, b) in chunk.iter_mut().enumerate() {
-                if i < bytes.len() {
-                    *b = bytes[i];
-                }
+/// Renames to a random name of the same length, then to a one-character
+/// name, and returns the final path.
+#[cfg(not(unix))]
+fn obfuscate_name(path: &Path, options: &SecureDeleteOptions, rng: &mut StdRng) -> Result<PathBuf> {
+    if !options.obfuscate_names {
+        return Ok(path.to_path_buf());
+    }
+    let Some(parent) = path.parent() else { return Ok(path.to_path_buf()) };
+    let length = path.file_name().map(|n| n.len()).unwrap_or(1).max(1);
+
+    let mut current = path.to_path_buf();
+    for length in [length, 1] {
+        let candidate = (0..16)
+            .map(|_| parent.join(random_name(rng, length)))
+            .find(|candidate| fs::symlink_metadata(candidate).is_err());
+        if let Some(candidate) = candidate {
+            fs::rename(&current, &candidate)?;
+            current = candidate;
+        }
+    }
+
+    Ok(current)
+}
+
+fn random_name(rng: &mut StdRng, length: usize) -> String {
+    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    (0..length)
+        .map(|_| CHARSET[rng.gen_range(0..CHARSET.len())] as char)
+        .collect()
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.nlink()
+}
+
+#[cfg(not(unix))]
+fn link_count(_metadata: &fs::Metadata) -> u64 {
+    1
+}
+
+/// What stands between overwriting `path` and its data actually being
+/// gone. Checked before the delete so the caller can warn the user.
+pub fn wipe_limitations(path: &Path) -> Vec<WipeLimitation> {
+    let mut limitations = device_limitations(path);
+
+    for entry in walkdir::WalkDir::new(path).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else { continue };
+        if link_count(&metadata) > 1 {
+            limitations.push(WipeLimitation::HardLinks {
+                path: entry.path().to_path_buf(),
+                links: link_count(&metadata),
+            });
+        }
+        #[cfg(target_os = "linux")]
+        if has_shared_extents(entry.path()) {
+            limitations.push(WipeLimitation::SharedExtents {
+                path: entry.path().to_path_buf(),
+            });
+        }
+    }
+
+    limitations
+}
+
+/// The filesystem and device level limitations for `path`, without
+/// looking at individual files.
+pub fn device_limitations(path: &Path) -> Vec<WipeLimitation> {
+    let mut limitations = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(mount) = crate::system::mounts::mount_for(path) {
+            if COPY_ON_WRITE_FILESYSTEMS.contains(&mount.file_system.as_str()) {
+                limitations.push(WipeLimitation::CopyOnWrite {
+                    file_system: mount.file_system.clone(),
+                    mount_point: mount.mount_point.clone(),
+                });
+            }
+            if let Some((device, trim)) = solid_state_device(path, &mount.device) {
+                limitations.push(WipeLimitation::SolidState { device, trim });
             }
         }
-        
-        let to_write = remaining.min(buffer.len());
-        file.write_all(&buffer[..to_write])?;
-        remaining -= to_write;
     }
-    
-    file.sync_all()?;
-    Ok(())
+
+    limitations
+}
+
+/// The block device behind `path` if it is non-rotational, and whether it
+/// accepts discards.
+#[cfg(target_os = "linux")]
+fn solid_state_device(path: &Path, mount_device: &str) -> Option<(String, bool)> {
+    use std::os::unix::fs::MetadataExt;
+
+    // btrfs and other multi-device filesystems report an anonymous st_dev;
+    // fall back to the device named in the mount table
+    let dev = fs::metadata(path).ok()?.dev();
+    let (major, minor) = (libc::major(dev), libc::minor(dev));
+    let sys_device = if major != 0 {
+        fs::canonicalize(format!("/sys/dev/block/{}:{}", major, minor)).ok()?
+    } else {
+        let name = Path::new(mount_device).file_name()?;
+        fs::canonicalize(Path::new("/sys/class/block").join(name)).ok()?
+    };
+    // Partitions keep their queue settings on the parent disk
+    let queue_owner = if sys_device.join("partition").exists() {
+        sys_device.parent()?.to_path_buf()
+    } else {
+        sys_device.clone()
+    };
+
+    let read = |name: &str| fs::read_to_string(queue_owner.join("queue").join(name)).ok();
+    if read("rotational")?.trim() != "0" {
+        return None;
+    }
+    let trim = read("discard_max_bytes")
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(|v| v > 0)
+        .unwrap_or(false);
+    let name = sys_device.file_name()?.to_string_lossy().to_string();
+    Some((format!("/dev/{}", name), trim))
+}
+
+/// Asks the filesystem (FIEMAP) whether any extent of the file is shared
+/// with another file.
+#[cfg(target_os = "linux")]
+fn has_shared_extents(path: &Path) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    const FS_IOC_FIEMAP: libc::c_ulong = 0xC020_660B;
+    const FIEMAP_FLAG_SYNC: u32 = 0x1;
+    const FIEMAP_EXTENT_LAST: u32 = 0x1;
+    const FIEMAP_EXTENT_SHARED: u32 = 0x2000;
+    const EXTENTS_PER_CALL: usize = 32;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct FiemapExtent {
+        logical: u64,
+        physical: u64,
+        length: u64,
+        reserved64: [u64; 2],
+        flags: u32,
+        reserved: [u32; 3],
+    }
+
+    #[repr(C)]
+    struct Fiemap {
+        start: u64,
+        length: u64,
+        flags: u32,
+        mapped_extents: u32,
+        extent_count: u32,
+        reserved: u32,
+        extents: [FiemapExtent; EXTENTS_PER_CALL],
+    }
+
+    let Ok(file) = File::open(path) else { return false };
+    let mut start = 0u64;
+    loop {
+        let mut request = Fiemap {
+            start,
+            length: u64::MAX,
+            flags: FIEMAP_FLAG_SYNC,
+            mapped_extents: 0,
+            extent_count: EXTENTS_PER_CALL as u32,
+            reserved: 0,
+            extents: [FiemapExtent::default(); EXTENTS_PER_CALL],
+        };
+        // SAFETY: `request` is a properly laid out fiemap with room for
+        // `extent_count` extents, and lives for the duration of the call.
+        let rc = unsafe { libc::ioctl(file.as_raw_fd(), FS_IOC_FIEMAP as _, &mut request as *mut Fiemap) };
+        if rc != 0 || request.mapped_extents == 0 {
+            return false;
+        }
+
+        let extents = &request.extents[..request.mapped_extents as usize];
+        if extents.iter().any(|e| e.flags & FIEMAP_EXTENT_SHARED != 0) {
+            return true;
+        }
+        let last = extents[extents.len() - 1];
+        if last.flags & FIEMAP_EXTENT_LAST != 0 {
+            return false;
+        }
+        start = last.logical + last.length;
+    }
 }
@@ -0,0 +1,578 @@
+// src-tauri/src/cleanup/trash_bin.rs
+use super::privacy::write_atomically;
+use super::recent_files::percent_decode;
+use super::unlink::remove_tree;
+use crate::error::{CleanerError, Result};
+use crate::paths::{path_from_bytes, serde_path};
+use crate::scanner::dir_usage;
+use crate::system::mounts;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TRASHINFO_EXTENSION: &str = "trashinfo";
+const DIRECTORY_SIZES_FILE: &str = "directorysizes";
+/// `DeletionDate` has second precision and is written a moment after the
+/// caller records the deletion, so matches are fuzzy.
+const DELETION_TIME_TOLERANCE_SECS: i64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    /// Name of the entry in `files/` (and, with `.trashinfo`, in `info/`).
+    /// Display form; `files_path` has the exact name.
+    pub name: String,
+    #[serde(with = "serde_path")]
+    pub original_path: PathBuf,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub size: u64,
+    pub is_dir: bool,
+    #[serde(with = "serde_path")]
+    pub files_path: PathBuf,
+    #[serde(with = "serde_path")]
+    pub info_path: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashBin {
+    /// The trash directory itself, e.g. `~/.local/share/Trash` or `/mnt/usb/.Trash-1000`.
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    /// Top directory of the mount for per-mount trashes; `None` for the home trash.
+    #[serde(with = "serde_path::option")]
+    pub top_dir: Option<PathBuf>,
+    pub items: Vec<TrashItem>,
+    /// Entries in `files/` without a `.trashinfo`.
+    #[serde(with = "serde_path::vec")]
+    pub orphaned_files: Vec<PathBuf>,
+    /// `.trashinfo` files whose entry in `files/` is gone.
+    #[serde(with = "serde_path::vec")]
+    pub orphaned_info: Vec<PathBuf>,
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashScanResult {
+    pub bins: Vec<TrashBin>,
+    pub total_items: u64,
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashPurgeResult {
+    pub purged: Vec<TrashItem>,
+    /// `.trashinfo` files without an entry in `files/` that were (or in a
+    /// dry run, would be) removed.
+    #[serde(with = "serde_path::vec")]
+    pub orphaned_info: Vec<PathBuf>,
+    #[serde(with = "serde_path::failures")]
+    pub failed: Vec<(PathBuf, String)>,
+    pub bytes_freed: u64,
+    pub was_dry_run: bool,
+}
+
+/// The freedesktop.org Trash specification: the home trash plus
+/// `$topdir/.Trash/$uid` and `$topdir/.Trash-$uid` on every mount.
+pub struct TrashAnalyzer;
+
+impl TrashAnalyzer {
+    pub fn scan() -> Result<TrashScanResult> {
+        let bins: Vec<TrashBin> = trash_directories()
+            .into_iter()
+            .map(|(path, top_dir)| read_bin(&path, top_dir))
+            .collect();
+
+        Ok(TrashScanResult {
+            total_items: bins.iter().map(|b| b.items.len() as u64).sum(),
+            total_size: bins.iter().map(|b| b.total_size).sum(),
+            bins,
+        })
+    }
+
+    /// Permanently removes items deleted more than `older_than_days` ago,
+    /// along with orphans from before the cutoff: entries of `files/`
+    /// without a `.trashinfo`, by mtime, and `.trashinfo` files without an
+    /// entry in `files/`, by `DeletionDate`. The spec writes the info file
+    /// before moving the payload, so a fresh one may just be mid-trash.
+    pub fn purge(older_than_days: u32, dry_run: bool) -> Result<TrashPurgeResult> {
+        let cutoff = Utc::now() - chrono::Duration::days(older_than_days as i64);
+        let mut result = TrashPurgeResult {
+            purged: Vec::new(),
+            orphaned_info: Vec::new(),
+            failed: Vec::new(),
+            bytes_freed: 0,
+            was_dry_run: dry_run,
+        };
+
+        for bin in Self::scan()?.bins {
+            let expired: Vec<&TrashItem> = bin
+                .items
+                .iter()
+                .filter(|item| item.deleted_at.map(|d| d < cutoff).unwrap_or(false))
+                .collect();
+            let orphans: Vec<TrashItem> = bin
+                .orphaned_files
+                .iter()
+                .filter_map(|path| orphaned_item(&bin.path, path))
+                .filter(|item| item.deleted_at.map(|d| d < cutoff).unwrap_or(false))
+                .collect();
+            let orphaned_info: Vec<&PathBuf> = bin
+                .orphaned_info
+                .iter()
+                .filter(|path| orphaned_info_date(path).map(|d| d < cutoff).unwrap_or(false))
+                .collect();
+
+            if dry_run {
+                result.bytes_freed += expired.iter().copied().chain(&orphans).map(|i| i.size).sum::<u64>();
+                result.purged.extend(expired.into_iter().cloned());
+                result.purged.extend(orphans);
+                result.orphaned_info.extend(orphaned_info.into_iter().cloned());
+                continue;
+            }
+
+            let mut removed_names = HashSet::new();
+            for item in expired {
+                match remove_item(item) {
+                    Ok(()) => {
+                        removed_names.insert(item.name.clone());
+                        result.bytes_freed += item.size;
+                        result.purged.push(item.clone());
+                    }
+                    Err(e) => result.failed.push((item.files_path.clone(), e.to_string())),
+                }
+            }
+
+            for orphan in orphans {
+                match remove_item_payload(&orphan) {
+                    Ok(()) => {
+                        result.bytes_freed += orphan.size;
+                        result.purged.push(orphan);
+                    }
+                    Err(e) => result.failed.push((orphan.files_path.clone(), e.to_string())),
+                }
+            }
+
+            for info in orphaned_info {
+                match fs::remove_file(info) {
+                    Ok(()) => result.orphaned_info.push(info.clone()),
+                    Err(e) => result.failed.push((info.clone(), e.to_string())),
+                }
+            }
+
+            if !removed_names.is_empty() {
+                if let Err(e) = prune_directory_sizes(&bin.path, &removed_names) {
+                    result.failed.push((bin.path.join(DIRECTORY_SIZES_FILE), e.to_string()));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// Moves a trashed file or directory back to `original`. When the same path
+/// was trashed more than once, the entry deleted closest to `deleted_at`
+/// wins; without a time, the most recent one does. If something already
+/// occupies `original`, the item is restored next to it under a new name.
+pub fn restore_from_trash(original: &Path, deleted_at: Option<DateTime<Utc>>) -> Result<PathBuf> {
+    let target = free_restore_path(original);
+    restore_from_trash_to(original, deleted_at, &target)?;
+    Ok(target)
+}
+
+/// Like `restore_from_trash`, but puts the item at `target`, which must
+/// not exist.
+pub fn restore_from_trash_to(original: &Path, deleted_at: Option<DateTime<Utc>>, target: &Path) -> Result<()> {
+    let candidates: Vec<TrashItem> = trash_directories()
+        .into_iter()
+        .flat_map(|(path, top_dir)| read_bin(&path, top_dir).items)
+        .filter(|item| item.original_path == original)
+        .collect();
+
+    let item = match deleted_at {
+        Some(when) => candidates
+            .into_iter()
+            .filter_map(|item| {
+                let distance = (item.deleted_at? - when).num_seconds().abs();
+                (distance <= DELETION_TIME_TOLERANCE_SECS).then_some((distance, item))
+            })
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, item)| item),
+        None => candidates.into_iter().max_by_key(|item| item.deleted_at),
+    }
+    .ok_or_else(|| CleanerError::FileNotFound(format!("{} is not in any trash", original.display())))?;
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match fs::rename(&item.files_path, target) {
+        Ok(()) => {}
+        // Only happens when the target is on another filesystem
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            copy_recursively(&item.files_path, target)?;
+            remove_item_payload(&item)?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    fs::remove_file(&item.info_path)?;
+    if item.is_dir {
+        let trash_dir = item.info_path.parent().and_then(|p| p.parent()).map(Path::to_path_buf);
+        if let Some(trash_dir) = trash_dir {
+            let _ = prune_directory_sizes(&trash_dir, &HashSet::from([item.name.clone()]));
+        }
+    }
+
+    Ok(())
+}
+
+/// `original`, or `name (restored N).ext` next to it when that is taken.
+pub(crate) fn free_restore_path(original: &Path) -> PathBuf {
+    if fs::symlink_metadata(original).is_err() {
+        return original.to_path_buf();
+    }
+
+    (1..)
+        .map(|n| {
+            let suffix = if n == 1 { " (restored)".to_string() } else { format!(" (restored {})", n) };
+            // Built from OS strings so a name that is not UTF-8 keeps its bytes
+            let mut name = original.file_stem().unwrap_or_default().to_os_string();
+            name.push(suffix);
+            if let Some(extension) = original.extension() {
+                name.push(".");
+                name.push(extension);
+            }
+            original.with_file_name(name)
+        })
+        .find(|candidate| fs::symlink_metadata(candidate).is_err())
+        .unwrap_or_else(|| original.to_path_buf())
+}
+
+/// Copies a file, symlink or whole tree to `to` without following links,
+/// keeping permissions, mtimes and extended attributes.
+pub(crate) fn copy_recursively(from: &Path, to: &Path) -> Result<()> {
+    let mut directories = Vec::new();
+    for entry in walkdir::WalkDir::new(from).follow_links(false) {
+        let entry = entry.map_err(|e| CleanerError::System(e.to_string()))?;
+        let relative = entry.path().strip_prefix(from).unwrap_or(entry.path());
+        let destination = if relative.as_os_str().is_empty() { to.to_path_buf() } else { to.join(relative) };
+        let file_type = entry.file_type();
+
+        if file_type.is_dir() {
+            fs::create_dir_all(&destination)?;
+            directories.push((entry.path().to_path_buf(), destination));
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &destination)?;
+        } else {
+            fs::copy(entry.path(), &destination)?;
+            copy_metadata(entry.path(), &destination);
+        }
+    }
+    // Deepest first, once nothing else is written into them
+    for (source, destination) in directories.iter().rev() {
+        copy_metadata(source, destination);
+    }
+    Ok(())
+}
+
+/// Best effort; `fs::copy` already carries the permission bits of files.
+fn copy_metadata(from: &Path, to: &Path) {
+    let Ok(metadata) = fs::metadata(from) else { return };
+    #[cfg(unix)]
+    if let Ok(names) = xattr::list(from) {
+        for name in names {
+            if let Ok(Some(value)) = xattr::get(from, &name) {
+                let _ = xattr::set(to, &name, &value);
+            }
+        }
+    }
+    let _ = fs::set_permissions(to, metadata.permissions());
+    if let (Ok(modified), Ok(file)) = (metadata.modified(), fs::File::open(to)) {
+        let _ = file.set_modified(modified);
+    }
+}
+
+/// Every trash directory that exists for the current user, paired with its
+/// mount's top directory (`None` for the home trash).
+pub fn trash_directories() -> Vec<(PathBuf, Option<PathBuf>)> {
+    let mut found: Vec<(PathBuf, Option<PathBuf>)> = Vec::new();
+    let mut seen = HashSet::new();
+
+    if let Some(home_trash) = dirs::data_dir().map(|d| d.join("Trash")) {
+        if home_trash.is_dir() {
+            seen.insert(home_trash.canonicalize().unwrap_or_else(|_| home_trash.clone()));
+            found.push((home_trash, None));
+        }
+    }
+
+    let uid = current_uid();
+    for mount in mounts::read_mounts() {
+        let top = mount.mount_point;
+        for candidate in [top.join(".Trash").join(uid.to_string()), top.join(format!(".Trash-{}", uid))] {
+            if !candidate.is_dir() || !is_valid_shared_trash(&top, &candidate) {
+                continue;
+            }
+            let canonical = candidate.canonicalize().unwrap_or_else(|_| candidate.clone());
+            if seen.insert(canonical) {
+                found.push((candidate, Some(top.clone())));
+            }
+        }
+    }
+
+    found
+}
+
+/// `$topdir/.Trash` must be a real directory with the sticky bit set,
+/// otherwise the spec says to ignore it (another user could have planted it).
+fn is_valid_shared_trash(top: &Path, candidate: &Path) -> bool {
+    let shared = top.join(".Trash");
+    if !candidate.starts_with(&shared) {
+        return true;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::symlink_metadata(&shared)
+            .map(|m| m.is_dir() && m.permissions().mode() & 0o1000 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+fn read_bin(path: &Path, top_dir: Option<PathBuf>) -> TrashBin {
+    let files_dir = path.join("files");
+    let info_dir = path.join("info");
+    let cached_sizes = read_directory_sizes(path);
+
+    let mut items = Vec::new();
+    let mut orphaned_info = Vec::new();
+    let mut named = HashSet::new();
+
+    for info_path in read_dir_paths(&info_dir) {
+        if info_path.extension().map(|e| e != TRASHINFO_EXTENSION).unwrap_or(true) {
+            continue;
+        }
+        let Some(stem) = info_path.file_stem().map(|s| s.to_os_string()) else { continue };
+        let name = stem.to_string_lossy().to_string();
+        let files_path = files_dir.join(&stem);
+
+        let Ok(metadata) = fs::symlink_metadata(&files_path) else {
+            orphaned_info.push(info_path);
+            continue;
+        };
+        named.insert(stem);
+
+        let (original, deleted_at) = parse_trashinfo(&info_path).unwrap_or((None, None));
+        // Relative paths in a per-mount trash are relative to the top directory
+        let original_path = match (original, &top_dir) {
+            (Some(p), Some(top)) if p.is_relative() => top.join(p),
+            (Some(p), _) => p,
+            (None, _) => PathBuf::new(),
+        };
+
+        let size = if metadata.is_dir() {
+            cached_directory_size(&cached_sizes, &name, &info_path).unwrap_or_else(|| dir_usage(&files_path).size)
+        } else {
+            metadata.len()
+        };
+
+        items.push(TrashItem {
+            name,
+            original_path,
+            deleted_at,
+            size,
+            is_dir: metadata.is_dir(),
+            files_path,
+            info_path,
+        });
+    }
+
+    let orphaned_files = read_dir_paths(&files_dir)
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .map(|n| !named.contains(n))
+                .unwrap_or(false)
+        })
+        .collect();
+
+    items.sort_by(|a, b| a.deleted_at.cmp(&b.deleted_at));
+
+    TrashBin {
+        path: path.to_path_buf(),
+        top_dir,
+        total_size: items.iter().map(|i| i.size).sum(),
+        items,
+        orphaned_files,
+        orphaned_info,
+    }
+}
+
+/// An entry of `files/` without a `.trashinfo`. Its original location is
+/// unknown, and its mtime stands in for the deletion date.
+fn orphaned_item(trash_dir: &Path, files_path: &Path) -> Option<TrashItem> {
+    let metadata = fs::symlink_metadata(files_path).ok()?;
+    let file_name = files_path.file_name()?;
+    let mut info_name = file_name.to_os_string();
+    info_name.push(".");
+    info_name.push(TRASHINFO_EXTENSION);
+
+    Some(TrashItem {
+        name: file_name.to_string_lossy().to_string(),
+        original_path: PathBuf::new(),
+        deleted_at: metadata.modified().ok().map(DateTime::<Utc>::from),
+        size: if metadata.is_dir() { dir_usage(files_path).size } else { metadata.len() },
+        is_dir: metadata.is_dir(),
+        files_path: files_path.to_path_buf(),
+        info_path: trash_dir.join("info").join(info_name),
+    })
+}
+
+/// How old a `.trashinfo` without an entry in `files/` is: its
+/// `DeletionDate`, or else its mtime.
+fn orphaned_info_date(info_path: &Path) -> Option<DateTime<Utc>> {
+    parse_trashinfo(info_path).and_then(|(_, deleted_at)| deleted_at).or_else(|| {
+        let modified = fs::symlink_metadata(info_path).ok()?.modified().ok()?;
+        Some(DateTime::<Utc>::from(modified))
+    })
+}
+
+/// Reads `Path=` and `DeletionDate=` from the `[Trash Info]` group. The date
+/// is local time without a zone, as the spec requires.
+fn parse_trashinfo(info_path: &Path) -> Option<(Option<PathBuf>, Option<DateTime<Utc>>)> {
+    let content = fs::read_to_string(info_path).ok()?;
+    let mut in_group = false;
+    let mut original = None;
+    let mut deleted_at = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_group = line == "[Trash Info]";
+            continue;
+        }
+        if !in_group {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix("Path=") {
+            original = Some(path_from_bytes(percent_decode(value)));
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deleted_at = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+                .ok()
+                .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+                .map(|local| local.with_timezone(&Utc));
+        }
+    }
+
+    Some((original, deleted_at))
+}
+
+/// `directorysizes` lines are `<size> <trashinfo mtime> <percent-encoded name>`.
+fn read_directory_sizes(trash_dir: &Path) -> HashMap<String, (u64, i64)> {
+    let Ok(content) = fs::read_to_string(trash_dir.join(DIRECTORY_SIZES_FILE)) else {
+        return HashMap::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ' ');
+            let size = fields.next()?.parse().ok()?;
+            let mtime = fields.next()?.parse().ok()?;
+            let name = String::from_utf8_lossy(&percent_decode(fields.next()?)).to_string();
+            Some((name, (size, mtime)))
+        })
+        .collect()
+}
+
+/// A cached size is only valid while the `.trashinfo` mtime still matches.
+fn cached_directory_size(cache: &HashMap<String, (u64, i64)>, name: &str, info_path: &Path) -> Option<u64> {
+    let (size, mtime) = cache.get(name)?;
+    let info_mtime = fs::metadata(info_path)
+        .ok()?
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?
+        .as_secs() as i64;
+    (info_mtime == *mtime).then_some(*size)
+}
+
+fn prune_directory_sizes(trash_dir: &Path, removed: &HashSet<String>) -> Result<()> {
+    let path = trash_dir.join(DIRECTORY_SIZES_FILE);
+    let Ok(content) = fs::read_to_string(&path) else { return Ok(()) };
+
+    let kept: String = content
+        .lines()
+        .filter(|line| {
+            let name = line.splitn(3, ' ').nth(2).map(|n| String::from_utf8_lossy(&percent_decode(n)).to_string());
+            name.map(|n| !removed.contains(&n)).unwrap_or(false)
+        })
+        .map(|line| format!("{}\n", line))
+        .collect();
+
+    write_atomically(&path, kept.as_bytes())
+}
+
+/// Removes the payload first, then its `.trashinfo`; an interrupted purge
+/// leaves an orphaned info file, which the next purge cleans up.
+fn remove_item(item: &TrashItem) -> Result<()> {
+    remove_item_payload(item)?;
+    fs::remove_file(&item.info_path)?;
+    Ok(())
+}
+
+fn remove_item_payload(item: &TrashItem) -> Result<()> {
+    if item.is_dir {
+        make_writable(&item.files_path);
+    }
+    remove_tree(&item.files_path, None, None)
+}
+
+/// Trashed trees can contain read-only directories (e.g. Go module caches)
+/// that would otherwise make the removal fail halfway.
+fn make_writable(dir: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        for entry in walkdir::WalkDir::new(dir).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                if let Ok(metadata) = entry.metadata() {
+                    let mut permissions = metadata.permissions();
+                    if permissions.mode() & 0o200 == 0 {
+                        permissions.set_mode(permissions.mode() | 0o700);
+                        let _ = fs::set_permissions(entry.path(), permissions);
+                    }
+                }
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+    }
+}
+
+fn current_uid() -> u32 {
+    #[cfg(unix)]
+    {
+        unsafe { libc::getuid() }
+    }
+    #[cfg(not(unix))]
+    {
+        0
+    }
+}
+
+fn read_dir_paths(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,466 @@
+// src-tauri/src/cleanup/unlink.rs
+use super::executor::CleanupProgress;
+use crate::error::Result;
+use crate::scanner::FileIdentity;
+use std::path::Path;
+
+/// Entries unlinked between two progress and cancellation checkpoints.
+const BATCH_SIZE: usize = 1024;
+
+/// Removes a file or a whole directory tree without following symlinks.
+///
+/// Everything happens relative to directory descriptors: the target is
+/// looked up in its opened parent, must still have the `expected` device
+/// and inode, and each subdirectory is opened with `O_NOFOLLOW`, so swapping
+/// a symlink into the tree while it is removed cannot redirect the delete
+/// elsewhere. Directories on another mount, including bind mounts of the
+/// same filesystem, are left in place. Read-only directories the user owns,
+/// like Go's module cache, are made writable before they are emptied.
+/// Entries are read in batches so a cache with hundreds of thousands of
+/// files can be cancelled between batches.
+pub fn remove_tree(path: &Path, expected: Option<FileIdentity>, progress: Option<&CleanupProgress>) -> Result<()> {
+    #[cfg(unix)]
+    {
+        unix::remove_tree(path, expected, progress)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (expected, progress);
+        let metadata = std::fs::symlink_metadata(path)?;
+        if metadata.file_type().is_symlink() || !metadata.is_dir() {
+            std::fs::remove_file(path)?;
+        } else {
+            std::fs::remove_dir_all(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renames `path` to `destination` through their opened parent
+/// directories, after checking it still has the `expected` device and inode.
+/// Fails instead of copying when the rename crosses filesystems.
+pub fn rename_checked(path: &Path, expected: Option<FileIdentity>, destination: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        unix::rename_checked(path, expected, destination)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = expected;
+        std::fs::rename(path, destination)?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+pub use unix::{remove_tree_with, UnlinkHook};
+
+#[cfg(unix)]
+mod unix {
+    use super::{CleanupProgress, FileIdentity, BATCH_SIZE};
+    use crate::error::{CleanerError, Result};
+    use std::ffi::{CStr, CString};
+    use std::io;
+    use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+
+    /// Acts on each entry `remove_tree_with` is about to unlink, files and
+    /// emptied directories alike.
+    pub trait UnlinkHook {
+        /// `dir` is the open directory holding `name` and `stat` its
+        /// `fstatat` without following symlinks. Returns the name to unlink,
+        /// which differs if the hook renamed the entry.
+        fn before_unlink(&mut self, dir: libc::c_int, name: &CStr, stat: &libc::stat) -> Result<CString>;
+    }
+
+    /// An open directory being emptied. Subdirectories found in a batch are
+    /// descended into one at a time, so only one descriptor per level is open.
+    struct Frame {
+        dir: *mut libc::DIR,
+        /// Name in the parent directory.
+        name: CString,
+        subdirs: Vec<CString>,
+        exhausted: bool,
+        /// A mount point below it was left alone, so it cannot be removed.
+        keep: bool,
+    }
+
+    impl Frame {
+        /// Takes ownership of `fd`.
+        fn new(fd: OwnedFd, name: CString) -> io::Result<Self> {
+            make_writable(&fd)?;
+            let dir = unsafe { libc::fdopendir(fd.as_raw_fd()) };
+            if dir.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            // Closed with the stream from now on
+            let _ = fd.into_raw_fd();
+            Ok(Self {
+                dir,
+                name,
+                subdirs: Vec::new(),
+                exhausted: false,
+                keep: false,
+            })
+        }
+
+        fn fd(&self) -> libc::c_int {
+            unsafe { libc::dirfd(self.dir) }
+        }
+
+        /// Reads up to `BATCH_SIZE` entries, unlinks the non-directories and
+        /// queues the directories. Returns how many entries were unlinked.
+        fn unlink_batch(&mut self, mut hook: Option<&mut (dyn UnlinkHook + '_)>) -> Result<u64> {
+            let mut names = Vec::with_capacity(BATCH_SIZE);
+            while names.len() < BATCH_SIZE {
+                // readdir only sets errno on failure, not at the end
+                clear_errno();
+                let entry = unsafe { libc::readdir(self.dir) };
+                if entry.is_null() {
+                    let error = io::Error::last_os_error();
+                    if error.raw_os_error() != Some(0) {
+                        return Err(error.into());
+                    }
+                    self.exhausted = true;
+                    break;
+                }
+                let name = unsafe { CStr::from_ptr((*entry).d_name.as_ptr()) };
+                if name.to_bytes() == b"." || name.to_bytes() == b".." {
+                    continue;
+                }
+                names.push((name.to_owned(), unsafe { (*entry).d_type }));
+            }
+
+            let mut unlinked = 0;
+            for (name, d_type) in names {
+                if self.is_dir(&name, d_type)? {
+                    self.subdirs.push(name);
+                    continue;
+                }
+                let name = match hook.as_deref_mut() {
+                    Some(hook) => match stat_at(self.fd(), &name) {
+                        Ok(stat) => hook.before_unlink(self.fd(), &name, &stat)?,
+                        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                            unlinked += 1;
+                            continue;
+                        }
+                        Err(e) => return Err(e.into()),
+                    },
+                    None => name,
+                };
+                // unlinkat removes a symlink itself, never its target
+                if unsafe { libc::unlinkat(self.fd(), name.as_ptr(), 0) } != 0 {
+                    let error = io::Error::last_os_error();
+                    if error.kind() != io::ErrorKind::NotFound {
+                        return Err(error.into());
+                    }
+                }
+                unlinked += 1;
+            }
+            Ok(unlinked)
+        }
+
+        fn is_dir(&self, name: &CStr, d_type: u8) -> io::Result<bool> {
+            if d_type != libc::DT_UNKNOWN {
+                return Ok(d_type == libc::DT_DIR);
+            }
+            Ok(stat_at(self.fd(), name)?.st_mode & libc::S_IFMT == libc::S_IFDIR)
+        }
+    }
+
+    impl Drop for Frame {
+        fn drop(&mut self) {
+            unsafe { libc::closedir(self.dir) };
+        }
+    }
+
+    /// Which mount a directory is on. A bind mount of the same filesystem
+    /// has the device number of its parent; only the mount id differs.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct MountKey {
+        device: libc::dev_t,
+        mount_id: Option<u64>,
+    }
+
+    fn mount_key(fd: libc::c_int) -> io::Result<MountKey> {
+        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+        if unsafe { libc::fstat(fd, &mut stat) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(MountKey {
+            device: stat.st_dev,
+            mount_id: mount_id(fd),
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn mount_id(fd: libc::c_int) -> Option<u64> {
+        let mut stx: libc::statx = unsafe { std::mem::zeroed() };
+        let empty = b"\0";
+        let found = unsafe {
+            libc::statx(fd, empty.as_ptr().cast(), libc::AT_EMPTY_PATH, libc::STATX_MNT_ID, &mut stx)
+        } == 0;
+        if found && stx.stx_mask & libc::STATX_MNT_ID != 0 {
+            return Some(stx.stx_mnt_id);
+        }
+        // Kernels before 5.8 only report it in fdinfo
+        std::fs::read_to_string(format!("/proc/self/fdinfo/{}", fd))
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("mnt_id:"))?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn mount_id(_fd: libc::c_int) -> Option<u64> {
+        None
+    }
+
+    pub(super) fn remove_tree(
+        path: &Path,
+        expected: Option<FileIdentity>,
+        progress: Option<&CleanupProgress>,
+    ) -> Result<()> {
+        remove_tree_impl(path, expected, progress, None)
+    }
+
+    /// `remove_tree`, with `hook` run on every entry right before it is
+    /// unlinked, e.g. to overwrite its contents.
+    pub fn remove_tree_with(
+        path: &Path,
+        expected: Option<FileIdentity>,
+        progress: Option<&CleanupProgress>,
+        hook: &mut dyn UnlinkHook,
+    ) -> Result<()> {
+        remove_tree_impl(path, expected, progress, Some(hook))
+    }
+
+    fn remove_tree_impl(
+        path: &Path,
+        expected: Option<FileIdentity>,
+        progress: Option<&CleanupProgress>,
+        mut hook: Option<&mut (dyn UnlinkHook + '_)>,
+    ) -> Result<()> {
+        let (parent, name) = split(path)?;
+        // Ancestors may be symlinks the user chose to scan through; the
+        // identity check below catches one swapped in since
+        let parent_fd = open_dir(libc::AT_FDCWD, &parent, false)?;
+        let stat = stat_at(parent_fd.as_raw_fd(), &name)?;
+        let found = identity(&stat);
+        if expected.map(|e| e != found).unwrap_or(false) {
+            return Err(CleanerError::Changed(path.display().to_string()));
+        }
+
+        if stat.st_mode & libc::S_IFMT != libc::S_IFDIR {
+            let name = match hook {
+                Some(hook) => hook.before_unlink(parent_fd.as_raw_fd(), &name, &stat)?,
+                None => name,
+            };
+            if unsafe { libc::unlinkat(parent_fd.as_raw_fd(), name.as_ptr(), 0) } != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+            return Ok(());
+        }
+
+        let root_fd = open_dir(parent_fd.as_raw_fd(), &name, true)?;
+        // Replaced between the lookup and the open
+        if identity(&fstat(&root_fd)?) != found {
+            return Err(CleanerError::Changed(path.display().to_string()));
+        }
+        let root_mount = mount_key(root_fd.as_raw_fd())?;
+        if root_mount != mount_key(parent_fd.as_raw_fd())? {
+            return Err(CleanerError::PermissionDenied(format!("{} is a mount point", path.display())));
+        }
+
+        let mounts = remove_contents(
+            path,
+            root_fd,
+            name,
+            root_mount,
+            parent_fd.as_raw_fd(),
+            progress,
+            hook.as_deref_mut(),
+        )?;
+        if !mounts.is_empty() {
+            let mounts: Vec<String> = mounts.iter().map(|m| m.display().to_string()).collect();
+            return Err(CleanerError::PermissionDenied(format!(
+                "{} contains mount points that were left in place: {}",
+                path.display(),
+                mounts.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Empties and removes the directory open as `root_fd`. Returns the
+    /// mount points found below it, which are not entered.
+    fn remove_contents(
+        path: &Path,
+        root_fd: OwnedFd,
+        root_name: CString,
+        root_mount: MountKey,
+        parent_fd: libc::c_int,
+        progress: Option<&CleanupProgress>,
+        mut hook: Option<&mut (dyn UnlinkHook + '_)>,
+    ) -> Result<Vec<PathBuf>> {
+        let mut mounts = Vec::new();
+        let mut stack = vec![Frame::new(root_fd, root_name)?];
+
+        while let Some(frame) = stack.last_mut() {
+            if let Some(name) = frame.subdirs.pop() {
+                let fd = open_dir(frame.fd(), &name, true)?;
+                if mount_key(fd.as_raw_fd())? != root_mount {
+                    frame.keep = true;
+                    mounts.push(relative(path, &stack, &name));
+                    continue;
+                }
+                stack.push(Frame::new(fd, name)?);
+                continue;
+            }
+
+            if !frame.exhausted {
+                let unlinked = frame.unlink_batch(hook.as_deref_mut())?;
+                if let Some(progress) = progress {
+                    progress.checkpoint(path, unlinked)?;
+                }
+                continue;
+            }
+
+            // Empty now; remove it from its parent
+            let done = stack.pop().expect("frame on the stack");
+            let parent = stack.last_mut();
+            if done.keep {
+                if let Some(parent) = parent {
+                    parent.keep = true;
+                }
+                continue;
+            }
+            let parent_fd = parent.map(|p| p.fd()).unwrap_or(parent_fd);
+            let name = match hook.as_deref_mut() {
+                Some(hook) => {
+                    let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+                    if unsafe { libc::fstat(done.fd(), &mut stat) } != 0 {
+                        return Err(io::Error::last_os_error().into());
+                    }
+                    hook.before_unlink(parent_fd, &done.name, &stat)?
+                }
+                None => done.name.clone(),
+            };
+            if unsafe { libc::unlinkat(parent_fd, name.as_ptr(), libc::AT_REMOVEDIR) } != 0 {
+                return Err(io::Error::last_os_error().into());
+            }
+        }
+        Ok(mounts)
+    }
+
+    pub(super) fn rename_checked(path: &Path, expected: Option<FileIdentity>, destination: &Path) -> Result<()> {
+        let (parent, name) = split(path)?;
+        let (dest_parent, dest_name) = split(destination)?;
+        let parent_fd = open_dir(libc::AT_FDCWD, &parent, false)?;
+        let dest_fd = open_dir(libc::AT_FDCWD, &dest_parent, false)?;
+
+        let found = identity(&stat_at(parent_fd.as_raw_fd(), &name)?);
+        if expected.map(|e| e != found).unwrap_or(false) {
+            return Err(CleanerError::Changed(path.display().to_string()));
+        }
+        let renamed = unsafe {
+            libc::renameat(parent_fd.as_raw_fd(), name.as_ptr(), dest_fd.as_raw_fd(), dest_name.as_ptr())
+        };
+        if renamed != 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        // Swapped between the check and the rename: put it back
+        if identity(&stat_at(dest_fd.as_raw_fd(), &dest_name)?) != found {
+            unsafe {
+                libc::renameat(dest_fd.as_raw_fd(), dest_name.as_ptr(), parent_fd.as_raw_fd(), name.as_ptr())
+            };
+            return Err(CleanerError::Changed(path.display().to_string()));
+        }
+        Ok(())
+    }
+
+    fn relative(root: &Path, stack: &[Frame], name: &CStr) -> PathBuf {
+        let mut path = root.to_path_buf();
+        for frame in stack.iter().skip(1) {
+            path.push(std::ffi::OsStr::from_bytes(frame.name.to_bytes()));
+        }
+        path.push(std::ffi::OsStr::from_bytes(name.to_bytes()));
+        path
+    }
+
+    fn split(path: &Path) -> io::Result<(CString, CString)> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidInput, format!("cannot remove {}", path.display()));
+        let name = path.file_name().ok_or_else(invalid)?;
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.as_os_str(),
+            _ => std::ffi::OsStr::new("."),
+        };
+        Ok((
+            CString::new(parent.as_bytes()).map_err(|_| invalid())?,
+            CString::new(name.as_bytes()).map_err(|_| invalid())?,
+        ))
+    }
+
+    fn open_dir(parent: libc::c_int, name: &CStr, no_follow: bool) -> io::Result<OwnedFd> {
+        let mut flags = libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC;
+        if no_follow {
+            flags |= libc::O_NOFOLLOW;
+        }
+        let fd = unsafe { libc::openat(parent, name.as_ptr(), flags) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    fn stat_at(dir: libc::c_int, name: &CStr) -> io::Result<libc::stat> {
+        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+        if unsafe { libc::fstatat(dir, name.as_ptr(), &mut stat, libc::AT_SYMLINK_NOFOLLOW) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(stat)
+    }
+
+    /// Adds write permission to a directory the user owns, so its entries
+    /// can be unlinked. Others' directories are left as they are.
+    fn make_writable(fd: &OwnedFd) -> io::Result<()> {
+        let stat = fstat(fd)?;
+        if stat.st_mode & libc::S_IWUSR != 0 || stat.st_uid != unsafe { libc::geteuid() } {
+            return Ok(());
+        }
+        if unsafe { libc::fchmod(fd.as_raw_fd(), (stat.st_mode & 0o7777) | libc::S_IWUSR) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn fstat(fd: &OwnedFd) -> io::Result<libc::stat> {
+        let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+        if unsafe { libc::fstat(fd.as_raw_fd(), &mut stat) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(stat)
+    }
+
+    fn identity(stat: &libc::stat) -> FileIdentity {
+        FileIdentity {
+            device: stat.st_dev as u64,
+            inode: stat.st_ino as u64,
+        }
+    }
+
+    fn clear_errno() {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        unsafe {
+            *libc::__errno_location() = 0
+        };
+        #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+        unsafe {
+            *libc::__error() = 0
+        };
+    }
+}
@@ -1,5 +1,11 @@
 // src-tauri/src/cli.rs
 use clap::{Parser, Subcommand};
+use chrono::{DateTime, Utc};
+use desktop_cleaner_lib::cleanup::{backup_usage, restore_files, AuditLog, BackupGc, BackupUsage, ConflictPolicy, Quarantine};
+use desktop_cleaner_lib::config::AppConfig;
+use desktop_cleaner_lib::{database, Result};
+use rusqlite::Connection;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "systemsweep-cli")]
@@ -19,9 +25,226 @@ enum Commands {
         #[arg(short, long)]
         id: String,
     },
+    /// Restore-point backups
+    Backups {
+        #[command(subcommand)]
+        command: BackupCommands,
+    },
+    /// Files waiting in quarantine for permanent deletion
+    Quarantine {
+        #[command(subcommand)]
+        command: QuarantineCommands,
+    },
+    /// Audit log of destructive actions
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackupCommands {
+    /// Show how much space restore-point backups use
+    Usage,
+    /// Expire restore points outside the configured retention limits
+    Gc {
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum QuarantineCommands {
+    /// List quarantined files
+    List,
+    /// Move quarantined files back, keeping both on a name clash
+    Restore {
+        /// Ids as shown by `list`
+        ids: Vec<i64>,
+        /// Replace whatever is at the original path now
+        #[arg(long)]
+        overwrite: bool,
+    },
+    /// Delete files whose grace period is over
+    Purge {
+        /// Ignore the grace period
+        #[arg(long)]
+        all: bool,
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditCommands {
+    /// Check the hash chain for tampering
+    Verify,
+    /// Print (or write) the entries logged in a time range
+    Export {
+        /// RFC 3339, e.g. 2024-01-31T00:00:00Z
+        #[arg(long)]
+        from: Option<DateTime<Utc>>,
+        #[arg(long)]
+        to: Option<DateTime<Utc>>,
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 fn main() {
-    let _cli = Cli::parse();
-    println!("SystemSweep CLI - Coming soon");
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Commands::Backups { command } => run_backups(command),
+        Commands::Quarantine { command } => run_quarantine(command),
+        Commands::Audit { command } => run_audit(command),
+        Commands::Scan { .. } | Commands::Clean { .. } => {
+            println!("SystemSweep CLI - Coming soon");
+            Ok(())
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run_quarantine(command: QuarantineCommands) -> Result<()> {
+    let db_path = data_db_path()?;
+    let app_data_dir = db_path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(app_data_dir)?;
+    database::init_database(&db_path)?;
+    let conn = Connection::open(&db_path)?;
+    let quarantine = Quarantine::new(app_data_dir);
+    let grace_days = AppConfig::load(app_data_dir)?.quarantine_days;
+
+    match command {
+        QuarantineCommands::List => {
+            for item in quarantine.items(&conn, grace_days)? {
+                println!(
+                    "{}\t{}\t{}\tpurge after {}",
+                    item.id,
+                    format_bytes(item.size),
+                    item.original_path.display(),
+                    item.purge_after.map(|t| t.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default()
+                );
+            }
+        }
+        QuarantineCommands::Restore { ids, overwrite } => {
+            let policy = if overwrite { ConflictPolicy::Overwrite } else { ConflictPolicy::KeepBoth };
+            let report = restore_files(&conn, app_data_dir, &ids, &policy)?;
+            for file in &report.files {
+                let restored_to = file.restored_to.as_deref().unwrap_or(&file.original_path);
+                println!("{:?}\t{}", file.outcome, restored_to.display());
+            }
+            for (path, error) in &report.failed {
+                eprintln!("failed\t{}: {}", path.display(), error);
+            }
+        }
+        QuarantineCommands::Purge { all, dry_run } => {
+            let result = quarantine.purge(&conn, if all { 0 } else { grace_days }, dry_run)?;
+            for item in &result.purged {
+                println!("{}\t{}", item.id, item.original_path.display());
+            }
+            println!(
+                "{} {} item(s), {} freed",
+                if dry_run { "Would purge" } else { "Purged" },
+                result.purged.len(),
+                format_bytes(result.bytes_freed)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_audit(command: AuditCommands) -> Result<()> {
+    let db_path = data_db_path()?;
+    let audit = AuditLog::new(db_path.parent().unwrap_or(Path::new(".")));
+
+    match command {
+        AuditCommands::Verify => {
+            let result = audit.verify()?;
+            if result.valid {
+                println!("OK: {} entries", result.entries);
+                if let Some(hash) = result.last_hash {
+                    println!("Last hash: {}", hash);
+                }
+                for line in result.repairs {
+                    println!("Line {}: a torn entry was cut off after a crash", line);
+                }
+            } else {
+                println!(
+                    "TAMPERED: line {}: {}",
+                    result.first_invalid_line.unwrap_or(0),
+                    result.problem.unwrap_or_default()
+                );
+                std::process::exit(2);
+            }
+        }
+        AuditCommands::Export { from, to, output } => {
+            let exported = match output {
+                Some(path) => {
+                    let mut out = std::io::BufWriter::new(std::fs::File::create(&path)?);
+                    let exported = audit.export(from, to, &mut out)?;
+                    std::io::Write::flush(&mut out)?;
+                    exported
+                }
+                None => audit.export(from, to, &mut std::io::stdout().lock())?,
+            };
+            eprintln!("{} entries exported", exported);
+        }
+    }
+    Ok(())
+}
+
+fn data_db_path() -> Result<PathBuf> {
+    database::default_db_path()
+        .ok_or_else(|| desktop_cleaner_lib::CleanerError::System("no data directory".to_string()))
+}
+
+fn run_backups(command: BackupCommands) -> Result<()> {
+    let db_path = data_db_path()?;
+    let app_data_dir = db_path.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(app_data_dir)?;
+    database::init_database(&db_path)?;
+    let conn = Connection::open(&db_path)?;
+
+    match command {
+        BackupCommands::Usage => print_usage(&backup_usage(&conn, app_data_dir)?),
+        BackupCommands::Gc { dry_run } => {
+            let retention = AppConfig::load(app_data_dir)?.backup_retention;
+            let result = BackupGc::new(&conn, app_data_dir, retention).run(dry_run)?;
+            for point in &result.expired {
+                println!("{:?}\t{}", point.reason, point.original_path.display());
+            }
+            println!(
+                "{} {} restore point(s), {} freed",
+                if dry_run { "Would expire" } else { "Expired" },
+                result.expired.len(),
+                format_bytes(result.freed_bytes)
+            );
+            print_usage(&result.usage);
+        }
+    }
+    Ok(())
+}
+
+fn print_usage(usage: &BackupUsage) {
+    println!("Restore points: {}", usage.restore_points);
+    println!("Backed-up data: {}", format_bytes(usage.logical_bytes));
+    println!("Space used:     {} in {} objects", format_bytes(usage.stored_bytes), usage.objects);
+    if let Some(oldest) = usage.oldest {
+        println!("Oldest:         {}", oldest.format("%Y-%m-%d %H:%M"));
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
 }
@@ -1,7 +1,19 @@
 // src-tauri/src/commands/mod.rs
-use crate::cleanup::{DeleteOptions, SafeDeleter, DeleteResult, RestorePoint, restore, restore_file};
+use crate::cleanup::{DeleteOptions, SafeDeleter, DeleteResult, RestorePoint, restore};
+use crate::cleanup::{ConflictPolicy, RestoreReport};
+use crate::cleanup::{PrivacyCleanResult, PrivacyCleaner, PrivacyOptions};
+use crate::cleanup::{RecentFilesCleaner, RecentFilesOptions, RecentFilesResult};
+use crate::cleanup::{TrashAnalyzer, TrashPurgeResult, TrashScanResult};
+use crate::cleanup::{Journal, JournalAction, JournalFile, JournalOperation, RecoveryPolicy, RecoveryResult};
+use crate::cleanup::{backup_usage, BackupGc, BackupGcResult, BackupUsage};
+use crate::cleanup::{FreeSpaceWipeOptions, FreeSpaceWipeResult, FreeSpaceWiper};
+use crate::cleanup::{AuditAction, AuditEvent, AuditLog, AuditOutcome, AuditVerification};
+use crate::cleanup::{Quarantine, QuarantinePurgeResult, QuarantinedItem};
+use crate::cleanup::{assess_paths, ConfirmationSummary, IrreversibleAction};
+use crate::cleanup::{history, CleanedFile, HistoryExportFormat, HistoryFileRow, JunkSource, ReclaimedGrouping, ReclaimedSpace};
 use crate::config::AppConfig;
-use crate::error::Result;
+use crate::error::{CleanerError, Result};
+use crate::paths::RawPath;
 use crate::scanner::*;
 use crate::state::{AppState, ScanProgress};
 use crate::startup;
@@ -32,7 +44,7 @@ pub async fn scan_system(state: State<'_, AppState>) -> Result<ScanResult> {
 
 #[tauri::command]
 pub async fn scan_directory(
-    path: String,
+    path: RawPath,
     options: Option<ScanOptions>,
     state: State<'_, AppState>,
 ) -> Result<ScanResult> {
@@ -45,7 +57,7 @@ pub async fn scan_directory(
 
 #[tauri::command]
 pub async fn scan_duplicates(
-    paths: Vec<String>,
+    paths: Vec<RawPath>,
     min_size_mb: Option<u64>,
     state: State<'_, AppState>,
 ) -> Result<DuplicateScanResult> {
@@ -61,7 +73,7 @@ pub async fn scan_duplicates(
 
 #[tauri::command]
 pub async fn scan_large_files(
-    paths: Vec<String>,
+    paths: Vec<RawPath>,
     min_size_mb: u64,
     top_n: Option<usize>,
     state: State<'_, AppState>,
@@ -75,7 +87,7 @@ pub async fn scan_large_files(
 
 #[tauri::command]
 pub async fn scan_old_files(
-    paths: Vec<String>,
+    paths: Vec<RawPath>,
     min_age_days: u32,
     state: State<'_, AppState>,
 ) -> Result<ScanResult> {
@@ -96,6 +108,58 @@ pub async fn scan_cache(state: State<'_, AppState>) -> Result<CacheScanResult> {
     CacheScanner::scan(Some(Arc::new(state.inner().clone())))
 }
 
+#[tauri::command]
+pub async fn scan_package_caches(
+    idle_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<PackageCacheScanResult> {
+    state.reset_scan();
+    PackageCacheScanner::new(idle_days.unwrap_or(DEFAULT_PACKAGE_IDLE_DAYS))
+        .scan(Some(Arc::new(state.inner().clone())))
+}
+
+#[tauri::command]
+pub async fn scan_build_artifacts(
+    paths: Vec<RawPath>,
+    min_idle_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<BuildArtifactResult> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let finder = BuildArtifactFinder::new(min_idle_days, None);
+
+    state.reset_scan();
+    finder.find(&paths, Some(Arc::new(state.inner().clone())))
+}
+
+#[tauri::command]
+pub async fn scan_cargo_targets(
+    paths: Vec<RawPath>,
+    max_age_days: u32,
+) -> Result<Vec<TargetPruneReport>> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let pruner = CargoTargetPruner::new(max_age_days);
+
+    find_target_dirs(&paths)
+        .iter()
+        .map(|target| pruner.analyze(target))
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_browser_profiles() -> Result<Vec<BrowserProfile>> {
+    Ok(BrowserScanner::scan())
+}
+
+#[tauri::command]
+pub async fn scan_downloads(
+    min_age_days: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Option<DownloadScanResult>> {
+    state.reset_scan();
+    let analyzer = min_age_days.map(DownloadAnalyzer::new).unwrap_or_default();
+    analyzer.analyze(Some(Arc::new(state.inner().clone())))
+}
+
 #[tauri::command]
 pub async fn cancel_scan(state: State<'_, AppState>) -> Result<()> {
     state.cancel_scan();
@@ -109,87 +173,362 @@ pub async fn get_scan_progress(state: State<'_, AppState>) -> Result<ScanProgres
 
 // ============ Cleanup Commands ============
 
+/// First step of `delete_files` without the trash (or of `secure_delete`
+/// with `secure`): summarizes what would be removed and issues the token
+/// that call has to pass back within `CONFIRMATION_TTL`.
+#[tauri::command]
+pub async fn prepare_delete(
+    paths: Vec<RawPath>,
+    secure: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<ConfirmationSummary> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let action = if secure.unwrap_or(false) { IrreversibleAction::SecureDelete } else { IrreversibleAction::Delete };
+    let state = state.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let (bytes, highest_risk) = assess_paths(&paths);
+        Ok(state.confirmations.issue(action, &paths, bytes, highest_risk))
+    })
+    .await
+    .map_err(|e| CleanerError::System(e.to_string()))?
+}
+
+/// Deleting without the trash needs `confirmation` from `prepare_delete`
+/// for the same paths, and `acknowledge_high_risk` when its summary asked
+/// for it.
 #[tauri::command]
 pub async fn delete_files(
-    paths: Vec<String>,
+    paths: Vec<RawPath>,
     use_trash: bool,
     create_restore_point: bool,
+    identities: Option<Vec<(RawPath, FileIdentity)>>,
+    confirmation: Option<String>,
+    acknowledge_high_risk: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<DeleteResult> {
-    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
-    let conn = Connection::open(&state.db_path)?;
-    
-    let deleter = SafeDeleter::new(
+    if !use_trash {
+        confirm(&state, IrreversibleAction::Delete, &paths, confirmation, acknowledge_high_risk)?;
+    }
+    run_deleter(
+        paths,
+        identities,
         DeleteOptions {
             dry_run: false,
             use_trash,
             create_restore_point,
             secure_delete: false,
+            ..Default::default()
         },
-        state.db_path.parent().unwrap(),
-    );
-    
-    deleter.delete_files(&paths, &conn)
+        &state,
+    )
+    .await
 }
 
 #[tauri::command]
 pub async fn delete_files_dry_run(
-    paths: Vec<String>,
+    paths: Vec<RawPath>,
+    identities: Option<Vec<(RawPath, FileIdentity)>>,
     state: State<'_, AppState>,
 ) -> Result<DeleteResult> {
-    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
-    let conn = Connection::open(&state.db_path)?;
-    
-    let deleter = SafeDeleter::new(
+    run_deleter(
+        paths,
+        identities,
         DeleteOptions {
             dry_run: true,
             ..Default::default()
         },
-        state.db_path.parent().unwrap(),
-    );
-    
-    deleter.delete_files(&paths, &conn)
+        &state,
+    )
+    .await
 }
 
+/// Needs `confirmation` from `prepare_delete` with `secure` set.
 #[tauri::command]
 pub async fn secure_delete(
-    paths: Vec<String>,
+    paths: Vec<RawPath>,
+    identities: Option<Vec<(RawPath, FileIdentity)>>,
+    confirmation: Option<String>,
+    acknowledge_high_risk: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<DeleteResult> {
-    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
-    let conn = Connection::open(&state.db_path)?;
-    
-    let deleter = SafeDeleter::new(
+    let config = AppConfig::load(state.db_path.parent().unwrap())?;
+    confirm(&state, IrreversibleAction::SecureDelete, &paths, confirmation, acknowledge_high_risk)?;
+
+    run_deleter(
+        paths,
+        identities,
         DeleteOptions {
             dry_run: false,
             use_trash: false,
             create_restore_point: false,
             secure_delete: true,
+            secure_delete_options: config.secure_delete_options(),
+            ..Default::default()
         },
-        state.db_path.parent().unwrap(),
-    );
-    
-    deleter.delete_files(&paths, &conn)
+        &state,
+    )
+    .await
+}
+
+/// Issues the token `wipe_free_space` needs for `mount_point`; `bytes` is
+/// the free space that will be filled.
+#[tauri::command]
+pub async fn prepare_wipe_free_space(mount_point: String, state: State<'_, AppState>) -> Result<ConfirmationSummary> {
+    let disk = mounted_disk(&mount_point)?;
+    Ok(state.confirmations.issue(
+        IrreversibleAction::WipeFreeSpace,
+        &[PathBuf::from(&mount_point)],
+        disk.available_space,
+        RiskLevel::Low,
+    ))
 }
 
+/// Fills the free space of one of the mounts `get_disk_usage` reports and
+/// removes the fill files again. Needs `confirmation` from
+/// `prepare_wipe_free_space`. Cancelled through `cancel_scan`.
 #[tauri::command]
-pub async fn move_to_trash(paths: Vec<String>) -> Result<Vec<String>> {
+pub async fn wipe_free_space(
+    mount_point: String,
+    options: Option<FreeSpaceWipeOptions>,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<FreeSpaceWipeResult> {
+    mounted_disk(&mount_point)?;
+    confirm_targets(
+        &state,
+        IrreversibleAction::WipeFreeSpace,
+        &[PathBuf::from(&mount_point)],
+        confirmation,
+        None,
+    )?;
+
+    state.reset_scan();
+    let state = Arc::new(state.inner().clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        FreeSpaceWiper::new(options.unwrap_or_default()).wipe(&PathBuf::from(mount_point), Some(state))
+    })
+    .await
+    .map_err(|e| CleanerError::System(e.to_string()))?
+}
+
+/// Renames files into the quarantine; they are purged after
+/// `quarantine_days` unless restored.
+#[tauri::command]
+pub async fn quarantine_files(
+    paths: Vec<RawPath>,
+    identities: Option<Vec<(RawPath, FileIdentity)>>,
+    state: State<'_, AppState>,
+) -> Result<DeleteResult> {
+    run_deleter(
+        paths,
+        identities,
+        DeleteOptions {
+            quarantine: true,
+            ..Default::default()
+        },
+        &state,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn get_quarantine(state: State<'_, AppState>) -> Result<Vec<QuarantinedItem>> {
+    let app_data_dir = state.db_path.parent().unwrap();
+    let conn = Connection::open(&state.db_path)?;
+    Quarantine::new(app_data_dir).items(&conn, AppConfig::load(app_data_dir)?.quarantine_days)
+}
+
+#[tauri::command]
+pub async fn restore_quarantined(
+    ids: Vec<i64>,
+    policy: Option<ConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<RestoreReport> {
+    let conn = Connection::open(&state.db_path)?;
+    restore::restore_files(&conn, state.db_path.parent().unwrap(), &ids, &policy.unwrap_or_default())
+}
+
+/// Issues the token `purge_quarantine` needs with `all`, for the items
+/// in the quarantine right now.
+#[tauri::command]
+pub async fn prepare_purge_quarantine(state: State<'_, AppState>) -> Result<ConfirmationSummary> {
+    let conn = Connection::open(&state.db_path)?;
+    let planned = Quarantine::new(state.db_path.parent().unwrap()).purge(&conn, 0, true)?;
+    Ok(state.confirmations.issue(
+        IrreversibleAction::PurgeQuarantine,
+        &quarantine_targets(&planned),
+        planned.bytes_freed,
+        RiskLevel::Medium,
+    ))
+}
+
+/// Deletes quarantined items whose grace period is over, or all of them
+/// with `all`; that needs `confirmation` from `prepare_purge_quarantine`
+/// and is refused if the quarantine changed since.
+#[tauri::command]
+pub async fn purge_quarantine(
+    dry_run: bool,
+    all: Option<bool>,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<QuarantinePurgeResult> {
+    let app_data_dir = state.db_path.parent().unwrap();
+    let conn = Connection::open(&state.db_path)?;
+    let quarantine = Quarantine::new(app_data_dir);
+    let all = all.unwrap_or(false);
+    if all && !dry_run {
+        let planned = quarantine.purge(&conn, 0, true)?;
+        confirm_targets(&state, IrreversibleAction::PurgeQuarantine, &quarantine_targets(&planned), confirmation, None)?;
+    }
+    let grace_days = if all { 0 } else { AppConfig::load(app_data_dir)?.quarantine_days };
+    quarantine.purge(&conn, grace_days, dry_run)
+}
+
+#[tauri::command]
+pub async fn move_to_trash(paths: Vec<RawPath>, state: State<'_, AppState>) -> Result<Vec<RawPath>> {
+    let app_data_dir = state.db_path.parent().unwrap();
+    let audit = AuditLog::new(app_data_dir);
+    let browser_profiles = BrowserScanner::discover_profiles();
     let mut moved = Vec::new();
+    let mut journaled = Vec::new();
+    // The first audit failure is reported once what was moved is journaled
+    let mut audited = Ok(());
     for path in paths {
-        if trash::delete(&path).is_ok() {
-            moved.push(path);
+        let event = AuditEvent::new(AuditAction::Trash, path.as_path());
+        if let Some(browser) = BrowserScanner::running_browser_for(path.as_path(), &browser_profiles) {
+            let reason = format!("{:?} is running; close it before cleaning", browser);
+            audited = audited.and(audit.record(event, AuditOutcome::Failed, Some(&reason)));
+            continue;
         }
+        let size = std::fs::symlink_metadata(path.as_path())
+            .map(|m| if m.is_dir() { dir_usage(path.as_path()).size } else { m.len() })
+            .unwrap_or(0);
+        // The deletion time picks the right `.trashinfo` when the operation is undone
+        let deleted_at = chrono::Utc::now();
+        match trash::delete(path.as_path()) {
+            Ok(()) => {
+                audited = audited.and(audit.record(event, AuditOutcome::Succeeded, None));
+                journaled.push((path.as_path().to_path_buf(), size, JournalAction::Trash, deleted_at));
+                moved.push(path);
+            }
+            Err(e) => audited = audited.and(audit.record(event, AuditOutcome::Failed, Some(&e.to_string()))),
+        }
+    }
+
+    if !journaled.is_empty() {
+        let conn = Connection::open(&state.db_path)?;
+        Journal::new(&conn, app_data_dir).record_completed("trash", None, &journaled)?;
     }
+    audited?;
     Ok(moved)
 }
 
+#[tauri::command]
+pub async fn clean_privacy(
+    options: Option<PrivacyOptions>,
+    state: State<'_, AppState>,
+) -> Result<PrivacyCleanResult> {
+    let conn = Connection::open(&state.db_path)?;
+    let cleaner = PrivacyCleaner::new(options.unwrap_or_default(), state.db_path.parent().unwrap());
+    cleaner.clean(&conn)
+}
+
+#[tauri::command]
+pub async fn clean_recent_files(
+    options: Option<RecentFilesOptions>,
+    state: State<'_, AppState>,
+) -> Result<Option<RecentFilesResult>> {
+    let conn = Connection::open(&state.db_path)?;
+    let cleaner = RecentFilesCleaner::new(options.unwrap_or_default(), state.db_path.parent().unwrap());
+    cleaner.clean(&conn)
+}
+
+#[tauri::command]
+pub async fn scan_trash() -> Result<TrashScanResult> {
+    TrashAnalyzer::scan()
+}
+
+/// Issues the token `purge_trash` needs for the items older than
+/// `older_than_days` that are in the trash right now.
+#[tauri::command]
+pub async fn prepare_purge_trash(older_than_days: u32, state: State<'_, AppState>) -> Result<ConfirmationSummary> {
+    let planned = TrashAnalyzer::purge(older_than_days, true)?;
+    Ok(state.confirmations.issue(
+        IrreversibleAction::PurgeTrash,
+        &trash_targets(&planned),
+        planned.bytes_freed,
+        RiskLevel::Low,
+    ))
+}
+
+/// Outside a dry run, needs `confirmation` from `prepare_purge_trash` with
+/// the same `older_than_days`, and is refused if the trash changed since.
+#[tauri::command]
+pub async fn purge_trash(
+    older_than_days: u32,
+    dry_run: bool,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<TrashPurgeResult> {
+    if !dry_run {
+        let planned = TrashAnalyzer::purge(older_than_days, true)?;
+        confirm_targets(&state, IrreversibleAction::PurgeTrash, &trash_targets(&planned), confirmation, None)?;
+    }
+    let result = TrashAnalyzer::purge(older_than_days, dry_run)?;
+    if !dry_run {
+        if !result.purged.is_empty() {
+            let purged: Vec<CleanedFile> = result
+                .purged
+                .iter()
+                .map(|item| CleanedFile {
+                    path: item.original_path.clone(),
+                    category: FileCategory::Unknown,
+                    rule_id: None,
+                    bytes: item.size,
+                })
+                .collect();
+            history::add_cleanup_history(&Connection::open(&state.db_path)?, "trash_purge", None, &purged)?;
+        }
+
+        let audit = AuditLog::new(state.db_path.parent().unwrap());
+        for item in &result.purged {
+            let event = AuditEvent::removed(AuditAction::Delete, &item.files_path).size(item.size);
+            audit.record(event, AuditOutcome::Succeeded, None)?;
+        }
+        for info in &result.orphaned_info {
+            audit.record(AuditEvent::removed(AuditAction::Delete, info), AuditOutcome::Succeeded, None)?;
+        }
+        for (path, error) in &result.failed {
+            audit.record(AuditEvent::new(AuditAction::Delete, path), AuditOutcome::Failed, Some(error))?;
+        }
+    }
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn restore_files(
-    restore_point_id: i64,
+    restore_point_ids: Vec<i64>,
+    policy: Option<ConflictPolicy>,
     state: State<'_, AppState>,
-) -> Result<PathBuf> {
+) -> Result<RestoreReport> {
+    let conn = Connection::open(&state.db_path)?;
+    restore::restore_files(
+        &conn,
+        state.db_path.parent().unwrap(),
+        &restore_point_ids,
+        &policy.unwrap_or_default(),
+    )
+}
+
+#[tauri::command]
+pub async fn restore_operation(
+    operation_id: i64,
+    policy: Option<ConflictPolicy>,
+    state: State<'_, AppState>,
+) -> Result<RestoreReport> {
     let conn = Connection::open(&state.db_path)?;
-    restore_file(&conn, restore_point_id)
+    restore::restore_operation(&conn, state.db_path.parent().unwrap(), operation_id, &policy.unwrap_or_default())
 }
 
 #[tauri::command]
@@ -197,7 +536,96 @@ pub async fn get_restore_points(
     state: State<'_, AppState>,
 ) -> Result<Vec<RestorePoint>> {
     let conn = Connection::open(&state.db_path)?;
-    restore::get_restore_points(&conn)
+    restore::get_restore_points(&conn, state.db_path.parent().unwrap())
+}
+
+#[tauri::command]
+pub async fn get_journal(
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<JournalOperation>> {
+    let conn = Connection::open(&state.db_path)?;
+    Journal::new(&conn, state.db_path.parent().unwrap()).operations(limit.unwrap_or(100))
+}
+
+#[tauri::command]
+pub async fn get_journal_files(
+    operation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<JournalFile>> {
+    let conn = Connection::open(&state.db_path)?;
+    Journal::new(&conn, state.db_path.parent().unwrap()).files(operation_id)
+}
+
+#[tauri::command]
+pub async fn undo_operation(
+    operation_id: i64,
+    state: State<'_, AppState>,
+) -> Result<RecoveryResult> {
+    let conn = Connection::open(&state.db_path)?;
+    Journal::new(&conn, state.db_path.parent().unwrap()).rollback(operation_id)
+}
+
+#[tauri::command]
+pub async fn get_interrupted_operations(
+    state: State<'_, AppState>,
+) -> Result<Vec<JournalOperation>> {
+    let conn = Connection::open(&state.db_path)?;
+    Journal::new(&conn, state.db_path.parent().unwrap()).interrupted()
+}
+
+#[tauri::command]
+pub async fn recover_operation(
+    operation_id: i64,
+    policy: RecoveryPolicy,
+    state: State<'_, AppState>,
+) -> Result<RecoveryResult> {
+    let conn = Connection::open(&state.db_path)?;
+    Journal::new(&conn, state.db_path.parent().unwrap()).recover(operation_id, policy)
+}
+
+#[tauri::command]
+pub async fn get_backup_usage(
+    state: State<'_, AppState>,
+) -> Result<BackupUsage> {
+    let conn = Connection::open(&state.db_path)?;
+    backup_usage(&conn, state.db_path.parent().unwrap())
+}
+
+/// Issues the token `run_backup_gc` needs for the restore points that
+/// have expired right now.
+#[tauri::command]
+pub async fn prepare_backup_gc(state: State<'_, AppState>) -> Result<ConfirmationSummary> {
+    let app_data_dir = state.db_path.parent().unwrap();
+    let config = AppConfig::load(app_data_dir)?;
+    let conn = Connection::open(&state.db_path)?;
+    let planned = BackupGc::new(&conn, app_data_dir, config.backup_retention).run(true)?;
+    Ok(state.confirmations.issue(
+        IrreversibleAction::BackupGc,
+        &backup_gc_targets(&planned),
+        planned.freed_bytes,
+        RiskLevel::Medium,
+    ))
+}
+
+/// Outside a dry run, needs `confirmation` from `prepare_backup_gc`, and is
+/// refused if other restore points expired since.
+#[tauri::command]
+pub async fn run_backup_gc(
+    dry_run: Option<bool>,
+    confirmation: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<BackupGcResult> {
+    let app_data_dir = state.db_path.parent().unwrap();
+    let config = AppConfig::load(app_data_dir)?;
+    let conn = Connection::open(&state.db_path)?;
+    let gc = BackupGc::new(&conn, app_data_dir, config.backup_retention);
+    let dry_run = dry_run.unwrap_or(false);
+    if !dry_run {
+        let planned = gc.run(true)?;
+        confirm_targets(&state, IrreversibleAction::BackupGc, &backup_gc_targets(&planned), confirmation, None)?;
+    }
+    gc.run(dry_run)
 }
 
 // ============ Startup Commands ============
@@ -208,8 +636,11 @@ pub async fn get_startup_items() -> Result<Vec<startup::StartupItem>> {
 }
 
 #[tauri::command]
-pub async fn toggle_startup_item(name: String, enabled: bool) -> Result<bool> {
-    startup::toggle_startup_item(&name, enabled).await
+pub async fn toggle_startup_item(name: String, enabled: bool, state: State<'_, AppState>) -> Result<bool> {
+    let result = startup::toggle_startup_item(&name, enabled).await;
+    let label = format!("{} ({})", name, if enabled { "enable" } else { "disable" });
+    audit_startup_change(&state, &label, &result)?;
+    result
 }
 
 #[tauri::command]
@@ -217,13 +648,83 @@ pub async fn add_startup_item(
     name: String,
     command: String,
     description: Option<String>,
+    state: State<'_, AppState>,
 ) -> Result<PathBuf> {
-    startup::add_startup_item(&name, &command, description.as_deref()).await
+    let result = startup::add_startup_item(&name, &command, description.as_deref()).await;
+    audit_startup_change(&state, &format!("{} (add: {})", name, command), &result)?;
+    result
+}
+
+/// First step of `remove_startup_item`; the token is tied to the entry's file.
+#[tauri::command]
+pub async fn prepare_remove_startup_item(name: String, state: State<'_, AppState>) -> Result<ConfirmationSummary> {
+    let item = find_startup_item(&name).await?;
+    let bytes = std::fs::symlink_metadata(&item.path).map(|m| m.len()).unwrap_or(0);
+    Ok(state
+        .confirmations
+        .issue(IrreversibleAction::RemoveStartupItem, &[item.path.clone()], bytes, item.risk_level()))
+}
+
+#[tauri::command]
+pub async fn remove_startup_item(
+    name: String,
+    confirmation: Option<String>,
+    acknowledge_high_risk: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<bool> {
+    let item = find_startup_item(&name).await?;
+    state.confirmations.redeem(
+        confirmation.as_deref(),
+        IrreversibleAction::RemoveStartupItem,
+        &[item.path],
+        acknowledge_high_risk.unwrap_or(false),
+    )?;
+    let result = startup::remove_startup_item(&name).await;
+    audit_startup_change(&state, &format!("{} (remove)", name), &result)?;
+    result
+}
+
+async fn find_startup_item(name: &str) -> Result<startup::StartupItem> {
+    startup::get_startup_items()
+        .await?
+        .into_iter()
+        .find(|item| item.name == name)
+        .ok_or_else(|| CleanerError::FileNotFound(format!("startup item {}", name)))
 }
 
+fn audit_startup_change<T>(state: &AppState, label: &str, result: &Result<T>) -> Result<()> {
+    let (outcome, error) = match result {
+        Ok(_) => (AuditOutcome::Succeeded, None),
+        Err(e) => (AuditOutcome::Failed, Some(e.to_string())),
+    };
+    AuditLog::new(state.db_path.parent().unwrap()).record(
+        AuditEvent::named(AuditAction::StartupChange, label),
+        outcome,
+        error.as_deref(),
+    )
+}
+
+// ============ Audit Log Commands ============
+
 #[tauri::command]
-pub async fn remove_startup_item(name: String) -> Result<bool> {
-    startup::remove_startup_item(&name).await
+pub async fn verify_audit_log(state: State<'_, AppState>) -> Result<AuditVerification> {
+    AuditLog::new(state.db_path.parent().unwrap()).verify()
+}
+
+/// Writes the audit lines logged between `from` and `to` (RFC 3339, both
+/// optional) to `destination` and returns how many were written.
+#[tauri::command]
+pub async fn export_audit_log(
+    destination: RawPath,
+    from: Option<String>,
+    to: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<u64> {
+    let (from, to) = (parse_time(from)?, parse_time(to)?);
+    let mut out = std::io::BufWriter::new(std::fs::File::create(destination.as_path())?);
+    let exported = AuditLog::new(state.db_path.parent().unwrap()).export(from, to, &mut out)?;
+    std::io::Write::flush(&mut out)?;
+    Ok(exported)
 }
 
 // ============ System Info Commands ============
@@ -234,8 +735,21 @@ pub async fn get_system_info() -> Result<system::SystemInfo> {
 }
 
 #[tauri::command]
-pub async fn get_disk_usage() -> Result<Vec<system::DiskInfo>> {
-    Ok(system::get_disk_usage())
+pub async fn get_disk_usage(state: State<'_, AppState>) -> Result<Vec<system::DiskInfo>> {
+    let mut disks = system::get_disk_usage();
+    let conn = Connection::open(&state.db_path)?;
+    
+    // Each quarantined item counts towards the disk with the longest matching mount point
+    for (path, size) in Quarantine::new(state.db_path.parent().unwrap()).pending_reclaim(&conn)? {
+        if let Some(disk) = disks
+            .iter_mut()
+            .filter(|d| path.starts_with(&d.mount_point))
+            .max_by_key(|d| d.mount_point.len())
+        {
+            disk.pending_reclaim += size;
+        }
+    }
+    Ok(disks)
 }
 
 #[tauri::command]
@@ -305,21 +819,22 @@ pub async fn remove_scheduled_task(
 
 #[tauri::command]
 pub async fn get_config(state: State<'_, AppState>) -> Result<AppConfig> {
-    // In a real app we'd load from disk
-    Ok(AppConfig::default())
+    AppConfig::load(state.db_path.parent().unwrap())
 }
 
 #[tauri::command]
 pub async fn save_config(
-    _config: AppConfig,
-    _state: State<'_, AppState>,
+    config: AppConfig,
+    state: State<'_, AppState>,
 ) -> Result<()> {
-    Ok(())
+    config.save(state.db_path.parent().unwrap())
 }
 
 #[tauri::command]
-pub async fn reset_config(_state: State<'_, AppState>) -> Result<AppConfig> {
-    Ok(AppConfig::default())
+pub async fn reset_config(state: State<'_, AppState>) -> Result<AppConfig> {
+    let config = AppConfig::default();
+    config.save(state.db_path.parent().unwrap())?;
+    Ok(config)
 }
 
 // ============ History Commands ============
@@ -333,7 +848,7 @@ pub async fn get_cleanup_history(
     let limit = limit.unwrap_or(100);
     
     let mut stmt = conn.prepare(
-        "SELECT id, timestamp, operation_type, files_count, bytes_cleaned, details 
+        "SELECT id, timestamp, operation_type, files_count, bytes_cleaned, details, operation_id 
          FROM cleanup_history 
          ORDER BY timestamp DESC 
          LIMIT ?"
@@ -346,22 +861,149 @@ pub async fn get_cleanup_history(
             "operation_type": row.get::<_, String>(2)?,
             "files_count": row.get::<_, i64>(3)?,
             "bytes_cleaned": row.get::<_, i64>(4)?,
-            "details": row.get::<_, Option<String>>(5)?
+            "details": row.get::<_, Option<String>>(5)?,
+            "operation_id": row.get::<_, Option<i64>>(6)?
         }))
     })?.filter_map(|r| r.ok()).collect();
     
     Ok(history)
 }
 
+#[tauri::command]
+pub async fn get_cleanup_history_files(history_id: i64, state: State<'_, AppState>) -> Result<Vec<HistoryFileRow>> {
+    let conn = Connection::open(&state.db_path)?;
+    history::cleanup_history_files(&conn, history_id)
+}
+
+#[tauri::command]
+pub async fn get_reclaimed_space(
+    group_by: ReclaimedGrouping,
+    since: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ReclaimedSpace>> {
+    let conn = Connection::open(&state.db_path)?;
+    history::reclaimed_space(&conn, group_by, parse_time(since)?)
+}
+
+#[tauri::command]
+pub async fn get_junk_sources(limit: Option<u32>, state: State<'_, AppState>) -> Result<Vec<JunkSource>> {
+    let conn = Connection::open(&state.db_path)?;
+    history::recurring_junk_sources(&conn, limit.unwrap_or(20))
+}
+
+/// Writes one row per cleaned file to `destination` and returns the row count.
+#[tauri::command]
+pub async fn export_cleanup_history(
+    destination: RawPath,
+    format: HistoryExportFormat,
+    since: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<u64> {
+    let conn = Connection::open(&state.db_path)?;
+    let mut out = std::io::BufWriter::new(std::fs::File::create(destination.as_path())?);
+    let exported = history::export_cleanup_history(&conn, format, parse_time(since)?, &mut out)?;
+    std::io::Write::flush(&mut out)?;
+    Ok(exported)
+}
+
 #[tauri::command]
 pub async fn clear_history(state: State<'_, AppState>) -> Result<()> {
     let conn = Connection::open(&state.db_path)?;
-    conn.execute("DELETE FROM cleanup_history", [])?;
-    Ok(())
+    history::clear_cleanup_history(&conn)
 }
 
 // ============ Utility Functions ============
 
+/// Runs a `SafeDeleter` on the blocking thread pool; progress is reported
+/// and cancelled like a scan. `identities` pair paths with their
+/// `ScannedFile::identity`, so nothing swapped in since the scan is deleted;
+/// they are pairs rather than a map because a path need not be a valid
+/// JSON object key.
+async fn run_deleter(
+    paths: Vec<RawPath>,
+    identities: Option<Vec<(RawPath, FileIdentity)>>,
+    options: DeleteOptions,
+    state: &AppState,
+) -> Result<DeleteResult> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    let identities = identities
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(path, identity)| (path.into(), identity))
+        .collect();
+    let state = state.clone();
+    state.reset_scan();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let conn = Connection::open(&state.db_path)?;
+        let deleter = SafeDeleter::new(options, state.db_path.parent().unwrap()).with_identities(identities);
+        deleter.delete_files(&paths, &conn, Some(Arc::new(state)))
+    })
+    .await
+    .map_err(|e| CleanerError::System(e.to_string()))?
+}
+
+/// Redeems the token an irreversible command was called with; it must have
+/// been issued for `action` on exactly `paths`.
+fn confirm(
+    state: &AppState,
+    action: IrreversibleAction,
+    paths: &[RawPath],
+    token: Option<String>,
+    acknowledge_high_risk: Option<bool>,
+) -> Result<()> {
+    let paths: Vec<PathBuf> = paths.iter().map(|p| p.as_path().to_path_buf()).collect();
+    confirm_targets(state, action, &paths, token, acknowledge_high_risk)
+}
+
+/// `confirm` for targets the command worked out itself, e.g. from a dry run.
+fn confirm_targets(
+    state: &AppState,
+    action: IrreversibleAction,
+    targets: &[PathBuf],
+    token: Option<String>,
+    acknowledge_high_risk: Option<bool>,
+) -> Result<()> {
+    state
+        .confirmations
+        .redeem(token.as_deref(), action, targets, acknowledge_high_risk.unwrap_or(false))
+}
+
+fn trash_targets(planned: &TrashPurgeResult) -> Vec<PathBuf> {
+    planned
+        .purged
+        .iter()
+        .map(|item| item.files_path.clone())
+        .chain(planned.orphaned_info.iter().cloned())
+        .collect()
+}
+
+fn quarantine_targets(planned: &QuarantinePurgeResult) -> Vec<PathBuf> {
+    planned.purged.iter().map(|item| item.quarantine_path.clone()).collect()
+}
+
+fn backup_gc_targets(planned: &BackupGcResult) -> Vec<PathBuf> {
+    planned.expired.iter().map(|point| point.original_path.clone()).collect()
+}
+
+fn mounted_disk(mount_point: &str) -> Result<system::DiskInfo> {
+    system::get_disk_usage()
+        .into_iter()
+        .find(|d| d.mount_point == mount_point)
+        .ok_or_else(|| CleanerError::System(format!("{} is not a mounted disk", mount_point)))
+}
+
+/// Parses an optional RFC 3339 time argument.
+fn parse_time(value: Option<String>) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+    value
+        .map(|v| {
+            chrono::DateTime::parse_from_rfc3339(&v)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|e| CleanerError::System(format!("Invalid time {}: {}", v, e)))
+        })
+        .transpose()
+}
+
 fn get_default_scan_paths() -> Vec<PathBuf> {
     let mut paths = Vec::new();
     
@@ -369,7 +1011,6 @@ fn get_default_scan_paths() -> Vec<PathBuf> {
     {
         if let Some(home) = dirs::home_dir() {
             paths.push(home.join(".cache"));
-            paths.push(home.join(".local/share/Trash"));
         }
         paths.push(PathBuf::from("/tmp"));
         paths.push(PathBuf::from("/var/tmp"));
@@ -1,14 +1,26 @@
 // src-tauri/src/config/mod.rs
+use crate::cleanup::{SecureDeleteOptions, WipeScheme};
+use crate::error::{CleanerError, Result};
 use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILE: &str = "config.toml";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub theme: String,
     pub language: String,
     pub auto_scan: bool,
     pub scan_hidden: bool,
     pub use_trash: bool,
+    pub secure_delete_scheme: WipeScheme,
+    /// Passes for the random scheme.
     pub secure_delete_passes: u32,
+    pub backup_retention: BackupRetention,
+    /// Days quarantined files are kept before they are deleted for good.
+    pub quarantine_days: u32,
 }
 
 impl Default for AppConfig {
@@ -19,7 +31,63 @@ impl Default for AppConfig {
             auto_scan: false,
             scan_hidden: false,
             use_trash: true,
+            secure_delete_scheme: WipeScheme::default(),
             secure_delete_passes: 3,
+            backup_retention: BackupRetention::default(),
+            quarantine_days: 7,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Reads `config.toml` from the app data directory, falling back to the
+    /// defaults when it does not exist yet.
+    pub fn load(app_data_dir: &Path) -> Result<Self> {
+        let path = config_path(app_data_dir);
+        if !path.exists() {
+            return Ok(Self::default());
         }
+        toml::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| CleanerError::InvalidConfig(format!("{}: {}", path.display(), e)))
     }
+
+    pub fn save(&self, app_data_dir: &Path) -> Result<()> {
+        let data = toml::to_string_pretty(self).map_err(|e| CleanerError::InvalidConfig(e.to_string()))?;
+        fs::create_dir_all(app_data_dir)?;
+        fs::write(config_path(app_data_dir), data)?;
+        Ok(())
+    }
+
+    pub fn secure_delete_options(&self) -> SecureDeleteOptions {
+        SecureDeleteOptions {
+            scheme: self.secure_delete_scheme,
+            passes: self.secure_delete_passes,
+            ..Default::default()
+        }
+    }
+}
+
+/// Limits on restore-point backups. Whichever limit is hit first expires
+/// the oldest restore points; `None` disables that limit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupRetention {
+    pub max_age_days: Option<u32>,
+    /// Bytes the backup store may take on disk, after dedup and compression.
+    pub max_total_bytes: Option<u64>,
+    pub max_restore_points: Option<u32>,
+}
+
+impl Default for BackupRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: Some(30),
+            max_total_bytes: Some(5 * 1024 * 1024 * 1024),
+            max_restore_points: Some(1000),
+        }
+    }
+}
+
+fn config_path(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join(CONFIG_FILE)
 }
@@ -1,7 +1,13 @@
 // src-tauri/src/database.rs
 use crate::error::Result;
 use rusqlite::{Connection, params};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Where the app keeps its database: Tauri's app data directory for the
+/// `com.desktopcleaner.app` identifier. For callers without an `AppHandle`.
+pub fn default_db_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("com.desktopcleaner.app").join("cleaner.db"))
+}
 
 pub fn init_database(db_path: &Path) -> Result<()> {
     let conn = Connection::open(db_path)?;
@@ -13,17 +19,66 @@ pub fn init_database(db_path: &Path) -> Result<()> {
             operation_type TEXT NOT NULL,
             files_count INTEGER NOT NULL,
             bytes_cleaned INTEGER NOT NULL,
-            details TEXT
+            details TEXT,
+            operation_id INTEGER
         );
         
-        CREATE TABLE IF NOT EXISTS restore_points (
+        CREATE TABLE IF NOT EXISTS cleanup_history_files (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            timestamp TEXT NOT NULL,
-            original_path TEXT NOT NULL,
-            backup_path TEXT NOT NULL,
-            file_hash TEXT,
+            history_id INTEGER NOT NULL REFERENCES cleanup_history(id),
+            path BLOB NOT NULL,
+            source BLOB NOT NULL,
+            category TEXT NOT NULL,
+            rule_id TEXT,
+            bytes INTEGER NOT NULL
+        );
+        
+        CREATE TABLE IF NOT EXISTS journal_operations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            reference TEXT,
+            status TEXT NOT NULL,
+            started_at TEXT NOT NULL,
+            finished_at TEXT
+        );
+        
+        CREATE TABLE IF NOT EXISTS journal_files (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            operation_id INTEGER NOT NULL REFERENCES journal_operations(id),
+            original_path BLOB NOT NULL,
+            action TEXT NOT NULL,
+            backup INTEGER NOT NULL DEFAULT 0,
+            backup_hash TEXT,
             file_size INTEGER NOT NULL,
-            restored INTEGER DEFAULT 0
+            quarantine_path BLOB,
+            device INTEGER,
+            inode INTEGER,
+            status TEXT NOT NULL,
+            processed_at TEXT,
+            error TEXT
+        );
+        
+        CREATE TABLE IF NOT EXISTS backup_objects (
+            hash TEXT PRIMARY KEY,
+            size INTEGER NOT NULL,
+            stored_size INTEGER NOT NULL,
+            compressed INTEGER NOT NULL,
+            created_at TEXT NOT NULL
+        );
+        
+        CREATE TABLE IF NOT EXISTS backup_entries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            journal_file_id INTEGER NOT NULL REFERENCES journal_files(id),
+            relative_path BLOB NOT NULL,
+            kind TEXT NOT NULL,
+            object_hash TEXT REFERENCES backup_objects(hash),
+            size INTEGER NOT NULL,
+            mode INTEGER NOT NULL,
+            uid INTEGER,
+            gid INTEGER,
+            modified TEXT,
+            link_target BLOB,
+            xattrs TEXT
         );
         
         CREATE TABLE IF NOT EXISTS scheduled_tasks (
@@ -38,7 +93,7 @@ pub fn init_database(db_path: &Path) -> Result<()> {
         );
         
         CREATE TABLE IF NOT EXISTS scan_cache (
-            path TEXT PRIMARY KEY,
+            path BLOB PRIMARY KEY,
             size INTEGER NOT NULL,
             modified TEXT NOT NULL,
             hash TEXT,
@@ -46,31 +101,62 @@ pub fn init_database(db_path: &Path) -> Result<()> {
         );
         
         CREATE INDEX IF NOT EXISTS idx_cleanup_timestamp ON cleanup_history(timestamp);
-        CREATE INDEX IF NOT EXISTS idx_restore_path ON restore_points(original_path);
+        CREATE INDEX IF NOT EXISTS idx_cleanup_files_history ON cleanup_history_files(history_id);
+        CREATE INDEX IF NOT EXISTS idx_cleanup_files_source ON cleanup_history_files(source);
+        CREATE INDEX IF NOT EXISTS idx_journal_status ON journal_operations(status);
+        CREATE INDEX IF NOT EXISTS idx_journal_reference ON journal_operations(reference);
+        CREATE INDEX IF NOT EXISTS idx_journal_files_operation ON journal_files(operation_id);
+        CREATE INDEX IF NOT EXISTS idx_journal_files_path ON journal_files(original_path);
+        CREATE INDEX IF NOT EXISTS idx_backup_entries_file ON backup_entries(journal_file_id);
+        CREATE INDEX IF NOT EXISTS idx_backup_entries_object ON backup_entries(object_hash);
         CREATE INDEX IF NOT EXISTS idx_scan_cache_hash ON scan_cache(hash);
     "#)?;
     
+    // Databases created before cleanups were linked to their journal operation
+    add_column_if_missing(&conn, "cleanup_history", "operation_id", "INTEGER")?;
+    add_column_if_missing(&conn, "journal_files", "quarantine_path", "BLOB")?;
+    add_column_if_missing(&conn, "journal_files", "device", "INTEGER")?;
+    add_column_if_missing(&conn, "journal_files", "inode", "INTEGER")?;
+    paths_to_blobs(&conn)?;
+    
     Ok(())
 }
 
-pub fn add_cleanup_history(
-    _conn: &Connection,
-    _operation_type: &str,
-    _files_count: i64,
-    _bytes_cleaned: i64,
-    _details: Option<&str>,
-) -> Result<()> {
-    // Implementation placeholder based on user's manual provided snippet
-    // In a real scenario I would use the conn to insert.
+/// Paths are stored as their raw bytes. Earlier versions wrote them as
+/// (lossy) TEXT, which would never compare equal to a BLOB of the same bytes.
+fn paths_to_blobs(conn: &Connection) -> Result<()> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version >= 1 {
+        return Ok(());
+    }
+    let columns = [
+        ("cleanup_history_files", "path"),
+        ("cleanup_history_files", "source"),
+        ("journal_files", "original_path"),
+        ("journal_files", "quarantine_path"),
+        ("backup_entries", "relative_path"),
+        ("backup_entries", "link_target"),
+        ("scan_cache", "path"),
+    ];
+    let tx = conn.unchecked_transaction()?;
+    for (table, column) in columns {
+        tx.execute_batch(&format!(
+            "UPDATE {table} SET {column} = CAST({column} AS BLOB) WHERE typeof({column}) = 'text'"
+        ))?;
+    }
+    tx.execute_batch("PRAGMA user_version = 1")?;
+    tx.commit()?;
     Ok(())
 }
 
-pub fn add_restore_point(
-    _conn: &Connection,
-    _original_path: &str,
-    _backup_path: &str,
-    _file_hash: Option<&str>,
-    _file_size: i64,
-) -> Result<i64> {
-    Ok(1)
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let exists = conn
+        .prepare(&format!("PRAGMA table_info({})", table))?
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|r| r.ok())
+        .any(|name| name == column);
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+    }
+    Ok(())
 }
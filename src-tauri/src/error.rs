@@ -18,6 +18,15 @@ pub enum CleanerError {
     #[error("File not found: {0}")]
     FileNotFound(String),
     
+    #[error("Backup is corrupt: {0}")]
+    Corrupted(String),
+    
+    #[error("File changed since it was scanned: {0}")]
+    Changed(String),
+    
+    #[error("Confirmation required: {0}")]
+    ConfirmationRequired(String),
+    
     #[error("Operation cancelled")]
     Cancelled,
     
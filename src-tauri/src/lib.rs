@@ -2,6 +2,7 @@ pub mod commands;
 pub mod config;
 pub mod database;
 pub mod error;
+pub mod paths;
 pub mod scanner;
 pub mod cleanup;
 pub mod startup;
@@ -33,6 +33,49 @@ fn main() {
             let db_path = app_dir.join("cleaner.db");
             desktop_cleaner_lib::database::init_database(&db_path)?;
             
+            // Operations a crash left half-done wait for the user to resume or roll back
+            match desktop_cleaner_lib::cleanup::detect_interrupted_operations(&db_path) {
+                Ok(interrupted) if !interrupted.is_empty() => {
+                    tracing::warn!("{} interrupted cleanup operation(s) found in the journal", interrupted.len());
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to check the cleanup journal: {}", e),
+            }
+            
+            let config = desktop_cleaner_lib::config::AppConfig::load(&app_dir).unwrap_or_default();
+            
+            // Expire restore points that fell outside the retention limits
+            let gc = rusqlite::Connection::open(&db_path)
+                .map_err(desktop_cleaner_lib::CleanerError::from)
+                .and_then(|conn| {
+                    desktop_cleaner_lib::cleanup::BackupGc::new(&conn, &app_dir, config.backup_retention.clone()).run(false)
+                });
+            match gc {
+                Ok(result) if !result.expired.is_empty() => tracing::info!(
+                    "Expired {} restore point(s), freed {} bytes of backups",
+                    result.expired.len(),
+                    result.freed_bytes
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Backup garbage collection failed: {}", e),
+            }
+            
+            // Delete quarantined files whose grace period is over
+            let purge = rusqlite::Connection::open(&db_path)
+                .map_err(desktop_cleaner_lib::CleanerError::from)
+                .and_then(|conn| {
+                    desktop_cleaner_lib::cleanup::Quarantine::new(&app_dir).purge(&conn, config.quarantine_days, false)
+                });
+            match purge {
+                Ok(result) if !result.purged.is_empty() => tracing::info!(
+                    "Purged {} quarantined item(s), freed {} bytes",
+                    result.purged.len(),
+                    result.bytes_freed
+                ),
+                Ok(_) => {}
+                Err(e) => tracing::error!("Quarantine purge failed: {}", e),
+            }
+            
             // Store db path in state
             app.manage(desktop_cleaner_lib::state::AppState::new(db_path));
 
@@ -76,26 +119,56 @@ fn main() {
             commands::scan_large_files,
             commands::scan_old_files,
             commands::scan_cache,
+            commands::scan_package_caches,
+            commands::scan_build_artifacts,
+            commands::scan_cargo_targets,
+            commands::get_browser_profiles,
+            commands::scan_downloads,
             commands::cancel_scan,
             commands::get_scan_progress,
             
             // Cleanup commands
+            commands::prepare_delete,
             commands::delete_files,
             commands::delete_files_dry_run,
             commands::secure_delete,
             commands::move_to_trash,
+            commands::quarantine_files,
+            commands::get_quarantine,
+            commands::restore_quarantined,
+            commands::prepare_purge_quarantine,
+            commands::purge_quarantine,
+            commands::clean_privacy,
+            commands::clean_recent_files,
+            commands::scan_trash,
+            commands::prepare_purge_trash,
+            commands::purge_trash,
             commands::restore_files,
+            commands::restore_operation,
             commands::get_restore_points,
+            commands::get_journal,
+            commands::get_journal_files,
+            commands::undo_operation,
+            commands::get_interrupted_operations,
+            commands::recover_operation,
+            commands::get_backup_usage,
+            commands::prepare_backup_gc,
+            commands::run_backup_gc,
+            commands::verify_audit_log,
+            commands::export_audit_log,
             
             // Startup management
             commands::get_startup_items,
             commands::toggle_startup_item,
             commands::add_startup_item,
+            commands::prepare_remove_startup_item,
             commands::remove_startup_item,
             
             // System info
             commands::get_system_info,
             commands::get_disk_usage,
+            commands::prepare_wipe_free_space,
+            commands::wipe_free_space,
             commands::get_memory_usage,
             commands::get_running_processes,
             
@@ -111,6 +184,10 @@ fn main() {
             
             // History
             commands::get_cleanup_history,
+            commands::get_cleanup_history_files,
+            commands::get_reclaimed_space,
+            commands::get_junk_sources,
+            commands::export_cleanup_history,
             commands::clear_history,
         ])
         .run(tauri::generate_context!())
@@ -0,0 +1,341 @@
+// src-tauri/src/paths.rs
+//! Paths are byte strings on Unix and need not be valid UTF-8. Everything
+//! that stores or sends a path keeps its exact bytes; `to_string_lossy()`
+//! is only for display.
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+/// The bytes of `path` as the OS sees them.
+pub fn path_to_bytes(path: &Path) -> Cow<'_, [u8]> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Cow::Borrowed(path.as_os_str().as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        match path.to_string_lossy() {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        }
+    }
+}
+
+pub fn path_from_bytes(bytes: Vec<u8>) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        PathBuf::from(std::ffi::OsString::from_vec(bytes))
+    }
+    #[cfg(not(unix))]
+    {
+        PathBuf::from(String::from_utf8_lossy(&bytes).to_string())
+    }
+}
+
+/// A path as it travels over IPC and through SQLite.
+///
+/// Over IPC a path that is valid UTF-8 is a plain string, which is both its
+/// exact bytes and its display form. Any other path is sent as
+/// `{ "display": "caf\u{FFFD}", "raw": [99, 97, 102, 233] }`; the frontend
+/// shows `display` and passes the whole value back unchanged. In SQLite it
+/// is stored as a BLOB; TEXT written by older versions is still read.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RawPath(pub PathBuf);
+
+impl RawPath {
+    pub fn as_path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl From<RawPath> for PathBuf {
+    fn from(path: RawPath) -> Self {
+        path.0
+    }
+}
+
+impl From<PathBuf> for RawPath {
+    fn from(path: PathBuf) -> Self {
+        Self(path)
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Repr<'a> {
+    Text(&'a str),
+    Raw { display: String, raw: Cow<'a, [u8]> },
+}
+
+impl<'a> Repr<'a> {
+    fn of(path: &'a Path) -> Self {
+        match path.to_str() {
+            Some(text) => Repr::Text(text),
+            None => Repr::Raw {
+                display: path.to_string_lossy().to_string(),
+                raw: path_to_bytes(path),
+            },
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OwnedRepr {
+    Text(String),
+    Raw { raw: Vec<u8> },
+}
+
+impl Serialize for RawPath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Repr::of(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawPath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match OwnedRepr::deserialize(deserializer)? {
+            OwnedRepr::Text(text) => RawPath(PathBuf::from(text)),
+            OwnedRepr::Raw { raw } => RawPath(path_from_bytes(raw)),
+        })
+    }
+}
+
+impl ToSql for RawPath {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(blob(&self.0))
+    }
+}
+
+impl FromSql for RawPath {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Blob(bytes) | ValueRef::Text(bytes) => Ok(RawPath(path_from_bytes(bytes.to_vec()))),
+            _ => Err(FromSqlError::InvalidType),
+        }
+    }
+}
+
+/// A borrowed path bound as a BLOB query parameter.
+pub struct SqlPath<'a>(pub &'a Path);
+
+impl ToSql for SqlPath<'_> {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(blob(self.0))
+    }
+}
+
+fn blob(path: &Path) -> ToSqlOutput<'_> {
+    match path_to_bytes(path) {
+        Cow::Borrowed(bytes) => ToSqlOutput::Borrowed(ValueRef::Blob(bytes)),
+        Cow::Owned(bytes) => ToSqlOutput::Owned(rusqlite::types::Value::Blob(bytes)),
+    }
+}
+
+/// `#[serde(with = "crate::paths::serde_path")]` for `PathBuf` fields, with
+/// submodules for the other shapes paths come in.
+pub mod serde_path {
+    use super::{RawPath, Repr};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::path::{Path, PathBuf};
+
+    pub fn serialize<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&Repr::of(path), serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<PathBuf, D::Error> {
+        RawPath::deserialize(deserializer).map(PathBuf::from)
+    }
+
+    pub mod option {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(path: &Option<PathBuf>, serializer: S) -> Result<S::Ok, S::Error> {
+            match path {
+                Some(path) => serializer.serialize_some(&Repr::of(path)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<PathBuf>, D::Error> {
+            Ok(Option::<RawPath>::deserialize(deserializer)?.map(PathBuf::from))
+        }
+    }
+
+    pub mod vec {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(paths: &[PathBuf], serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(paths.iter().map(|p| Repr::of(p)))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<PathBuf>, D::Error> {
+            Ok(Vec::<RawPath>::deserialize(deserializer)?.into_iter().map(PathBuf::from).collect())
+        }
+    }
+
+    /// `(path, error)` pairs of failed files.
+    pub mod failures {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(failures: &[(PathBuf, String)], serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(failures.iter().map(|(p, e)| (Repr::of(p), e)))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(PathBuf, String)>, D::Error> {
+            Ok(Vec::<(RawPath, String)>::deserialize(deserializer)?
+                .into_iter()
+                .map(|(p, e)| (p.into(), e))
+                .collect())
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::cleanup::{Journal, JournalAction};
+    use crate::scanner::{FileScanner, ScanOptions};
+    use rusqlite::Connection;
+    use std::fs;
+
+    /// "café" in Latin-1 and a name that is not valid in any encoding.
+    const LATIN1: &[u8] = b"caf\xe9.tmp";
+    const INVALID: &[u8] = b"\xff\xfe-\xc3(.log";
+
+    fn name(bytes: &[u8]) -> PathBuf {
+        path_from_bytes(bytes.to_vec())
+    }
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("systemsweep-paths-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn utf8_paths_are_sent_as_plain_strings() {
+        let path = RawPath(PathBuf::from("/home/user/café.tmp"));
+        let json = serde_json::to_string(&path).unwrap();
+        assert_eq!(json, "\"/home/user/café.tmp\"");
+        assert_eq!(serde_json::from_str::<RawPath>(&json).unwrap(), path);
+    }
+
+    #[test]
+    fn non_utf8_paths_round_trip_through_json() {
+        for bytes in [LATIN1, INVALID] {
+            let path = RawPath(Path::new("/tmp").join(name(bytes)));
+            let value = serde_json::to_value(&path).unwrap();
+            assert!(value["display"].as_str().unwrap().contains('\u{FFFD}'));
+            assert_eq!(value["raw"].as_array().unwrap().len(), path_to_bytes(&path.0).len());
+            assert_eq!(serde_json::from_value::<RawPath>(value).unwrap(), path);
+        }
+    }
+
+    #[test]
+    fn serde_path_fields_keep_their_bytes() {
+        #[derive(Serialize, Deserialize)]
+        struct Result {
+            #[serde(with = "serde_path::vec")]
+            deleted: Vec<PathBuf>,
+            #[serde(with = "serde_path::failures")]
+            failed: Vec<(PathBuf, String)>,
+            #[serde(with = "serde_path::option")]
+            restored_to: Option<PathBuf>,
+        }
+
+        let result = Result {
+            deleted: vec![name(LATIN1), PathBuf::from("plain")],
+            failed: vec![(name(INVALID), "Permission denied".to_string())],
+            restored_to: Some(name(LATIN1)),
+        };
+        let back: Result = serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+        assert_eq!(back.deleted, result.deleted);
+        assert_eq!(back.failed, result.failed);
+        assert_eq!(back.restored_to, result.restored_to);
+    }
+
+    #[test]
+    fn paths_round_trip_through_sqlite() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE t (path BLOB NOT NULL)").unwrap();
+        for bytes in [LATIN1, INVALID] {
+            conn.execute("INSERT INTO t (path) VALUES (?1)", [SqlPath(&name(bytes))]).unwrap();
+        }
+        // Written as TEXT by older versions
+        conn.execute("INSERT INTO t (path) VALUES ('/tmp/old.log')", []).unwrap();
+
+        let paths: Vec<PathBuf> = conn
+            .prepare("SELECT path FROM t ORDER BY rowid")
+            .unwrap()
+            .query_map([], |row| row.get::<_, RawPath>(0))
+            .unwrap()
+            .map(|r| r.unwrap().into())
+            .collect();
+        assert_eq!(paths, vec![name(LATIN1), name(INVALID), PathBuf::from("/tmp/old.log")]);
+    }
+
+    #[test]
+    fn journal_deletes_files_with_non_utf8_names() {
+        let dir = temp_dir("journal");
+        let db_path = dir.join("cleaner.db");
+        crate::database::init_database(&db_path).unwrap();
+        let conn = Connection::open(&db_path).unwrap();
+        let data = dir.join("data");
+        fs::create_dir(&data).unwrap();
+
+        let files: Vec<PathBuf> = [LATIN1, INVALID].iter().map(|b| data.join(name(b))).collect();
+        for file in &files {
+            fs::write(file, b"junk").unwrap();
+        }
+
+        let journal = Journal::new(&conn, &dir);
+        let entries: Vec<_> = files.iter().map(|f| (f.clone(), JournalAction::Delete, true)).collect();
+        let id = journal.begin("delete", None, &entries).unwrap();
+        for file in journal.files(id).unwrap() {
+            assert!(files.contains(&file.original_path));
+            journal.execute(&file).unwrap();
+        }
+        assert!(files.iter().all(|f| fs::symlink_metadata(f).is_err()));
+
+        // The backups restore under the exact original names
+        let ids: Vec<i64> = journal.files(id).unwrap().iter().map(|f| f.id).collect();
+        let report = crate::cleanup::restore::restore_files(&conn, &dir, &ids, &Default::default()).unwrap();
+        assert!(report.failed.is_empty(), "{:?}", report.failed);
+        assert!(files.iter().all(|f| fs::read(f).unwrap() == b"junk"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn exclude_patterns_match_non_utf8_names() {
+        let dir = temp_dir("exclude");
+        fs::write(dir.join(name(LATIN1)), b"junk").unwrap();
+        fs::write(dir.join(name(INVALID)), b"junk").unwrap();
+        let nested = dir.join(name(b"d\xe9j\xe0"));
+        fs::create_dir(&nested).unwrap();
+        fs::write(nested.join("inside.log"), b"junk").unwrap();
+
+        let scanner = FileScanner::new(ScanOptions {
+            include_hidden: true,
+            exclude_patterns: vec!["*.tmp".to_string()],
+            exclude_paths: vec![nested.clone()],
+            ..Default::default()
+        });
+        let found: Vec<PathBuf> = scanner
+            .scan(&[dir.clone()], None)
+            .unwrap()
+            .files
+            .into_iter()
+            .map(|f| f.path)
+            .filter(|p| p.is_file())
+            .collect();
+        assert_eq!(found, vec![dir.join(name(INVALID))]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,439 @@
+// src-tauri/src/scanner/browser.rs
+use super::*;
+use crate::paths::serde_path;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum BrowserKind {
+    Firefox,
+    Chrome,
+    Chromium,
+    Brave,
+    Edge,
+    Vivaldi,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum InstallKind {
+    Native,
+    Flatpak,
+    Snap,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum BrowserCacheKind {
+    Cache,
+    CodeCache,
+    GpuCache,
+    ServiceWorkerCache,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserCacheDir {
+    pub kind: BrowserCacheKind,
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserProfile {
+    pub browser: BrowserKind,
+    pub install: InstallKind,
+    /// Display name from profiles.ini / Local State.
+    pub name: String,
+    #[serde(with = "serde_path")]
+    pub path: PathBuf,
+    pub caches: Vec<BrowserCacheDir>,
+    pub running: bool,
+}
+
+impl BrowserProfile {
+    pub fn cache_size(&self, kind: BrowserCacheKind) -> u64 {
+        self.caches.iter().filter(|c| c.kind == kind).map(|c| c.size).sum()
+    }
+}
+
+/// Where one installation keeps its profiles and their disk caches.
+struct BrowserInstall {
+    browser: BrowserKind,
+    install: InstallKind,
+    /// Firefox: directory holding profiles.ini. Chromium: the "User Data" directory.
+    data_dir: PathBuf,
+    /// Root that mirrors `data_dir` for disk caches (often under ~/.cache).
+    cache_dir: PathBuf,
+}
+
+pub struct BrowserScanner;
+
+impl BrowserScanner {
+    /// Every profile of every supported browser, with cache sizes measured.
+    pub fn scan() -> Vec<BrowserProfile> {
+        let mut profiles = Self::discover_profiles();
+        for profile in &mut profiles {
+            for cache in &mut profile.caches {
+                cache.size = dir_usage(&cache.path).size;
+            }
+        }
+        profiles
+    }
+
+    /// Profiles and their cache directories, without measuring anything.
+    pub fn discover_profiles() -> Vec<BrowserProfile> {
+        Self::discover(true)
+    }
+
+    /// Every cache directory of every discovered profile. Does not check
+    /// which browsers are running.
+    pub fn cache_directories() -> Vec<PathBuf> {
+        Self::discover(false)
+            .into_iter()
+            .flat_map(|p| p.caches.into_iter().map(|c| c.path))
+            .collect()
+    }
+
+    fn discover(check_running: bool) -> Vec<BrowserProfile> {
+        let mut profiles = Vec::new();
+
+        for install in browser_installs() {
+            if !install.data_dir.is_dir() {
+                continue;
+            }
+            match install.browser {
+                BrowserKind::Firefox => profiles.extend(firefox_profiles(&install, check_running)),
+                _ => profiles.extend(chromium_profiles(&install, check_running)),
+            }
+        }
+
+        profiles
+    }
+
+    /// Returns the browser that owns `path` if that browser is running.
+    /// Nothing inside a live profile may be deleted.
+    pub fn running_browser_for(path: &Path, profiles: &[BrowserProfile]) -> Option<BrowserKind> {
+        profiles
+            .iter()
+            .filter(|p| p.running)
+            .find(|p| path.starts_with(&p.path) || p.caches.iter().any(|c| path.starts_with(&c.path)))
+            .map(|p| p.browser.clone())
+    }
+}
+
+fn browser_installs() -> Vec<BrowserInstall> {
+    let mut installs = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    {
+        let Some(home) = dirs::home_dir() else { return installs };
+        let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+        let cache = dirs::cache_dir().unwrap_or_else(|| home.join(".cache"));
+
+        let mut push = |browser: BrowserKind, install: InstallKind, data_dir: PathBuf, cache_dir: PathBuf| {
+            installs.push(BrowserInstall { browser, install, data_dir, cache_dir });
+        };
+
+        push(BrowserKind::Firefox, InstallKind::Native, home.join(".mozilla/firefox"), cache.join("mozilla/firefox"));
+        push(
+            BrowserKind::Firefox,
+            InstallKind::Flatpak,
+            home.join(".var/app/org.mozilla.firefox/.mozilla/firefox"),
+            home.join(".var/app/org.mozilla.firefox/cache/mozilla/firefox"),
+        );
+        push(
+            BrowserKind::Firefox,
+            InstallKind::Snap,
+            home.join("snap/firefox/common/.mozilla/firefox"),
+            home.join("snap/firefox/common/.cache/mozilla/firefox"),
+        );
+
+        let chromium_family = [
+            (BrowserKind::Chrome, "google-chrome", "com.google.Chrome"),
+            (BrowserKind::Chromium, "chromium", "org.chromium.Chromium"),
+            (BrowserKind::Brave, "BraveSoftware/Brave-Browser", "com.brave.Browser"),
+            (BrowserKind::Edge, "microsoft-edge", "com.microsoft.Edge"),
+            (BrowserKind::Vivaldi, "vivaldi", "com.vivaldi.Vivaldi"),
+        ];
+        for (browser, rel, flatpak_id) in chromium_family {
+            push(browser.clone(), InstallKind::Native, config.join(rel), cache.join(rel));
+            let flatpak = home.join(".var/app").join(flatpak_id);
+            push(browser, InstallKind::Flatpak, flatpak.join("config").join(rel), flatpak.join("cache").join(rel));
+        }
+
+        push(
+            BrowserKind::Chromium,
+            InstallKind::Snap,
+            home.join("snap/chromium/common/chromium"),
+            home.join("snap/chromium/common/.cache/chromium"),
+        );
+        push(
+            BrowserKind::Brave,
+            InstallKind::Snap,
+            home.join("snap/brave/current/.config/BraveSoftware/Brave-Browser"),
+            home.join("snap/brave/common/.cache/BraveSoftware/Brave-Browser"),
+        );
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        if let (Some(roaming), Some(local)) = (dirs::data_dir(), dirs::data_local_dir()) {
+            installs.push(BrowserInstall {
+                browser: BrowserKind::Firefox,
+                install: InstallKind::Native,
+                data_dir: roaming.join("Mozilla/Firefox"),
+                cache_dir: local.join("Mozilla/Firefox"),
+            });
+            for (browser, rel) in [
+                (BrowserKind::Chrome, "Google/Chrome/User Data"),
+                (BrowserKind::Chromium, "Chromium/User Data"),
+                (BrowserKind::Brave, "BraveSoftware/Brave-Browser/User Data"),
+                (BrowserKind::Edge, "Microsoft/Edge/User Data"),
+                (BrowserKind::Vivaldi, "Vivaldi/User Data"),
+            ] {
+                installs.push(BrowserInstall {
+                    browser,
+                    install: InstallKind::Native,
+                    data_dir: local.join(rel),
+                    cache_dir: local.join(rel),
+                });
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if let Some(home) = dirs::home_dir() {
+            let support = home.join("Library/Application Support");
+            let caches = home.join("Library/Caches");
+            installs.push(BrowserInstall {
+                browser: BrowserKind::Firefox,
+                install: InstallKind::Native,
+                data_dir: support.join("Firefox"),
+                cache_dir: caches.join("Firefox"),
+            });
+            for (browser, rel) in [
+                (BrowserKind::Chrome, "Google/Chrome"),
+                (BrowserKind::Chromium, "Chromium"),
+                (BrowserKind::Brave, "BraveSoftware/Brave-Browser"),
+                (BrowserKind::Edge, "Microsoft Edge"),
+                (BrowserKind::Vivaldi, "Vivaldi"),
+            ] {
+                installs.push(BrowserInstall {
+                    browser,
+                    install: InstallKind::Native,
+                    data_dir: support.join(rel),
+                    cache_dir: caches.join(rel),
+                });
+            }
+        }
+    }
+
+    installs
+}
+
+/// Parses profiles.ini. Relative profile paths are resolved against both the
+/// data directory and the cache root, which mirrors it.
+fn firefox_profiles(install: &BrowserInstall, check_running: bool) -> Vec<BrowserProfile> {
+    let Ok(content) = fs::read_to_string(install.data_dir.join("profiles.ini")) else {
+        return vec![];
+    };
+
+    let mut profiles = Vec::new();
+    for section in parse_ini(&content) {
+        if !section.name.starts_with("Profile") {
+            continue;
+        }
+        let Some(rel_path) = section.get("Path") else { continue };
+        let is_relative = section.get("IsRelative").map(|v| v == "1").unwrap_or(true);
+
+        let (path, cache_path) = if is_relative {
+            (install.data_dir.join(rel_path), install.cache_dir.join(rel_path))
+        } else {
+            (PathBuf::from(rel_path), PathBuf::from(rel_path))
+        };
+        if !path.is_dir() {
+            continue;
+        }
+
+        let mut caches = Vec::new();
+        push_cache(&mut caches, BrowserCacheKind::Cache, cache_path.join("cache2"));
+        push_cache(&mut caches, BrowserCacheKind::Cache, path.join("cache2"));
+        push_cache(&mut caches, BrowserCacheKind::CodeCache, cache_path.join("startupCache"));
+        push_cache(&mut caches, BrowserCacheKind::GpuCache, path.join("shader-cache"));
+        // Cache API storage of service workers lives in storage/default/<origin>/cache
+        if let Ok(origins) = fs::read_dir(path.join("storage/default")) {
+            for origin in origins.filter_map(|e| e.ok()) {
+                push_cache(&mut caches, BrowserCacheKind::ServiceWorkerCache, origin.path().join("cache"));
+            }
+        }
+
+        profiles.push(BrowserProfile {
+            browser: BrowserKind::Firefox,
+            install: install.install.clone(),
+            name: section.get("Name").unwrap_or(rel_path).to_string(),
+            running: check_running && is_firefox_running(&path, &install.install),
+            path,
+            caches,
+        });
+    }
+
+    profiles
+}
+
+/// Reads `Local State` to enumerate profile directories ("Default", "Profile 1", ...).
+fn chromium_profiles(install: &BrowserInstall, check_running: bool) -> Vec<BrowserProfile> {
+    let local_state = fs::read_to_string(install.data_dir.join("Local State"))
+        .ok()
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok());
+
+    let mut names: Vec<(String, String)> = local_state
+        .as_ref()
+        .and_then(|v| v.pointer("/profile/info_cache"))
+        .and_then(|v| v.as_object())
+        .map(|cache| {
+            cache
+                .iter()
+                .map(|(dir, info)| {
+                    let name = info.get("name").and_then(|n| n.as_str()).unwrap_or(dir);
+                    (dir.clone(), name.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if names.is_empty() && install.data_dir.join("Default").is_dir() {
+        names.push(("Default".to_string(), "Default".to_string()));
+    }
+
+    let running = check_running && is_chromium_running(&install.data_dir, &install.install);
+
+    names
+        .into_iter()
+        .filter(|(dir, _)| install.data_dir.join(dir).is_dir())
+        .map(|(dir, name)| {
+            let path = install.data_dir.join(&dir);
+            let cache_path = install.cache_dir.join(&dir);
+            let mut caches = Vec::new();
+
+            for root in [&cache_path, &path] {
+                push_cache(&mut caches, BrowserCacheKind::Cache, root.join("Cache"));
+                push_cache(&mut caches, BrowserCacheKind::CodeCache, root.join("Code Cache"));
+                push_cache(&mut caches, BrowserCacheKind::GpuCache, root.join("GPUCache"));
+            }
+            push_cache(&mut caches, BrowserCacheKind::GpuCache, path.join("DawnCache"));
+            push_cache(&mut caches, BrowserCacheKind::ServiceWorkerCache, path.join("Service Worker/CacheStorage"));
+            push_cache(&mut caches, BrowserCacheKind::ServiceWorkerCache, path.join("Service Worker/ScriptCache"));
+
+            BrowserProfile {
+                browser: install.browser.clone(),
+                install: install.install.clone(),
+                name,
+                path,
+                caches,
+                running,
+            }
+        })
+        .collect()
+}
+
+fn push_cache(caches: &mut Vec<BrowserCacheDir>, kind: BrowserCacheKind, path: PathBuf) {
+    if path.is_dir() && !caches.iter().any(|c| c.path == path) {
+        caches.push(BrowserCacheDir { kind, path, size: 0 });
+    }
+}
+
+/// Firefox keeps a `lock` symlink pointing at "<ip>:+<pid>" while running.
+fn is_firefox_running(profile: &Path, install: &InstallKind) -> bool {
+    #[cfg(unix)]
+    {
+        match fs::read_link(profile.join("lock")) {
+            Ok(target) => {
+                let target = target.to_string_lossy().to_string();
+                target
+                    .rsplit_once('+')
+                    .and_then(|(_, pid)| pid.parse::<u32>().ok())
+                    .map(|pid| is_pid_alive(pid, install))
+                    .unwrap_or(true)
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = install;
+        is_locked_for_writing(&profile.join("parent.lock"))
+    }
+}
+
+/// Chromium keeps a `SingletonLock` symlink pointing at "<host>-<pid>" while running.
+fn is_chromium_running(data_dir: &Path, install: &InstallKind) -> bool {
+    #[cfg(unix)]
+    {
+        match fs::read_link(data_dir.join("SingletonLock")) {
+            Ok(target) => {
+                let target = target.to_string_lossy().to_string();
+                target
+                    .rsplit_once('-')
+                    .and_then(|(_, pid)| pid.parse::<u32>().ok())
+                    .map(|pid| is_pid_alive(pid, install))
+                    .unwrap_or(true)
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let _ = install;
+        is_locked_for_writing(&data_dir.join("lockfile"))
+    }
+}
+
+/// Flatpak and Snap browsers record a PID from their own sandbox, which
+/// the host's `/proc` knows nothing about, so their lock is authoritative.
+#[cfg(unix)]
+fn is_pid_alive(pid: u32, install: &InstallKind) -> bool {
+    if cfg!(target_os = "linux") && *install == InstallKind::Native {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        // No cheap check without /proc; treat the lock as authoritative
+        true
+    }
+}
+
+#[cfg(windows)]
+fn is_locked_for_writing(path: &Path) -> bool {
+    path.exists() && fs::OpenOptions::new().write(true).open(path).is_err()
+}
+
+struct IniSection {
+    name: String,
+    entries: Vec<(String, String)>,
+}
+
+impl IniSection {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str())
+    }
+}
+
+fn parse_ini(content: &str) -> Vec<IniSection> {
+    let mut sections: Vec<IniSection> = Vec::new();
+
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            sections.push(IniSection {
+                name: line[1..line.len() - 1].to_string(),
+                entries: Vec::new(),
+            });
+        } else if let (Some(section), Some((key, value))) = (sections.last_mut(), line.split_once('=')) {
+            section.entries.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    sections
+}
@@ -0,0 +1,283 @@
+// src-tauri/src/scanner/build_artifacts.rs
+use super::*;
+use crate::error::{CleanerError, Result};
+use crate::paths::serde_path;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+/// Directories that are never searched for project roots.
+const PRUNED_DIRS: &[&str] = &[".git", ".hg", ".svn", "node_modules", "target", "__pycache__", ".venv", "venv"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ProjectKind {
+    Cargo,
+    Node,
+    Python,
+    Gradle,
+    CMake,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectArtifacts {
+    #[serde(with = "serde_path")]
+    pub root: PathBuf,
+    pub kinds: Vec<ProjectKind>,
+    /// Newest modification time of any file outside the build outputs.
+    pub last_source_change: Option<DateTime<Utc>>,
+    pub days_since_change: Option<i64>,
+    pub artifacts: Vec<ScannedFile>,
+    pub reclaimable_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildArtifactResult {
+    /// Projects ordered from the longest untouched to the most recently changed.
+    pub projects: Vec<ProjectArtifacts>,
+    pub total_size: u64,
+    pub scan_duration_ms: u64,
+}
+
+pub struct BuildArtifactFinder {
+    min_idle_days: Option<u32>,
+    max_depth: Option<usize>,
+}
+
+impl BuildArtifactFinder {
+    pub fn new(min_idle_days: Option<u32>, max_depth: Option<usize>) -> Self {
+        Self {
+            min_idle_days,
+            max_depth,
+        }
+    }
+
+    pub fn find(&self, paths: &[PathBuf], state: Option<Arc<AppState>>) -> Result<BuildArtifactResult> {
+        let start = std::time::Instant::now();
+        let local_rules = LocalRules::new();
+        let mut projects = Vec::new();
+        let mut seen_roots = HashSet::new();
+
+        for base_path in paths {
+            let walker = WalkDir::new(base_path)
+                .follow_links(false)
+                .max_depth(self.max_depth.unwrap_or(usize::MAX))
+                .into_iter()
+                .filter_entry(|e| {
+                    e.file_type().is_dir()
+                        && !is_pruned_dir(e.file_name())
+                        && !local_rules.is_ignored(e.path(), true)
+                });
+
+            for entry in walker.filter_map(|e| e.ok()) {
+                if let Some(ref s) = state {
+                    if s.is_cancelled() {
+                        return Err(CleanerError::Cancelled);
+                    }
+                }
+
+                let dir = entry.path();
+                let kinds = detect_project_kinds(dir);
+                if kinds.is_empty() || !seen_roots.insert(dir.to_path_buf()) {
+                    continue;
+                }
+
+                if let Some(ref s) = state {
+                    s.update_progress(crate::state::ScanProgress {
+                        current_path: dir.display().to_string(),
+                        files_scanned: seen_roots.len() as u64,
+                        bytes_scanned: 0,
+                        files_found: projects.len() as u64,
+                        bytes_found: projects.iter().map(|p: &ProjectArtifacts| p.reclaimable_size).sum(),
+                        phase: "build_artifacts".to_string(),
+                        percentage: 0.0,
+                    });
+                }
+
+                if let Some(project) = self.inspect_project(dir, kinds) {
+                    projects.push(project);
+                }
+            }
+        }
+
+        projects.sort_by(|a, b| match (a.days_since_change, b.days_since_change) {
+            (Some(a_days), Some(b_days)) => b_days.cmp(&a_days),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.reclaimable_size.cmp(&a.reclaimable_size),
+        });
+
+        Ok(BuildArtifactResult {
+            total_size: projects.iter().map(|p| p.reclaimable_size).sum(),
+            projects,
+            scan_duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn inspect_project(&self, root: &Path, kinds: Vec<ProjectKind>) -> Option<ProjectArtifacts> {
+        let mut artifact_dirs: Vec<(PathBuf, RiskLevel, &'static str)> = Vec::new();
+        for kind in &kinds {
+            for (name, risk, description) in artifact_candidates(kind) {
+                let path = root.join(name);
+                if path.is_dir() && is_artifact_dir(kind, &path) && !artifact_dirs.iter().any(|(p, _, _)| *p == path) {
+                    artifact_dirs.push((path, risk, description));
+                }
+            }
+        }
+        if kinds.contains(&ProjectKind::CMake) {
+            for dir in read_dir_names(root).filter(|n| n.to_string_lossy().starts_with("cmake-build-")) {
+                artifact_dirs.push((root.join(dir), RiskLevel::Low, "CMake build tree"));
+            }
+        }
+
+        // Walk the sources once, skipping outputs, to find the last real change.
+        // __pycache__ can live anywhere, so it is collected along the way.
+        let skip: HashSet<PathBuf> = artifact_dirs.iter().map(|(p, _, _)| p.clone()).collect();
+        let mut last_source_change: Option<DateTime<Utc>> = None;
+        let mut walker = WalkDir::new(root).follow_links(false).into_iter();
+
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else { continue };
+            let path = entry.path();
+
+            if entry.file_type().is_dir() {
+                if skip.contains(path) || entry.file_name() == ".git" || entry.file_name() == "node_modules" {
+                    walker.skip_current_dir();
+                } else if entry.file_name() == "__pycache__" {
+                    artifact_dirs.push((path.to_path_buf(), RiskLevel::Safe, "Python bytecode cache"));
+                    walker.skip_current_dir();
+                }
+                continue;
+            }
+
+            if let Some(modified) = entry.metadata().ok().and_then(|m| m.modified().ok()) {
+                let modified = DateTime::<Utc>::from(modified);
+                last_source_change = Some(last_source_change.map_or(modified, |m| m.max(modified)));
+            }
+        }
+
+        let days_since_change = last_source_change.map(|t| (Utc::now() - t).num_days());
+        if let (Some(min_days), Some(days)) = (self.min_idle_days, days_since_change) {
+            if days < min_days as i64 {
+                return None;
+            }
+        }
+
+        let artifacts: Vec<ScannedFile> = artifact_dirs
+            .into_iter()
+            .map(|(path, risk, description)| {
+                let usage = dir_usage(&path);
+                ScannedFile::from_directory(&path, &usage, FileCategory::Cache, risk, Some(description.to_string()))
+            })
+            .filter(|a| a.size > 0)
+            .collect();
+
+        if artifacts.is_empty() {
+            return None;
+        }
+
+        Some(ProjectArtifacts {
+            root: root.to_path_buf(),
+            kinds,
+            last_source_change,
+            days_since_change,
+            reclaimable_size: artifacts.iter().map(|a| a.size).sum(),
+            artifacts,
+        })
+    }
+}
+
+impl Default for BuildArtifactFinder {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+fn is_pruned_dir(name: &std::ffi::OsStr) -> bool {
+    PRUNED_DIRS.iter().any(|p| name == *p)
+}
+
+fn read_dir_names(dir: &Path) -> impl Iterator<Item = OsString> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name())
+}
+
+pub fn detect_project_kinds(dir: &Path) -> Vec<ProjectKind> {
+    let names: HashSet<OsString> = read_dir_names(dir).collect();
+    let has = |name: &str| names.contains(&OsString::from(name));
+    let mut kinds = Vec::new();
+
+    if has("Cargo.toml") {
+        kinds.push(ProjectKind::Cargo);
+    }
+    if has("package.json") {
+        kinds.push(ProjectKind::Node);
+    }
+    if has("pyproject.toml")
+        || has("setup.py")
+        || has("requirements.txt")
+        || dir.join(".venv/pyvenv.cfg").is_file()
+        || dir.join("venv/pyvenv.cfg").is_file()
+    {
+        kinds.push(ProjectKind::Python);
+    }
+    if has("build.gradle") || has("build.gradle.kts") || has("settings.gradle") || has("settings.gradle.kts") {
+        kinds.push(ProjectKind::Gradle);
+    }
+    if has("CMakeLists.txt") {
+        kinds.push(ProjectKind::CMake);
+    }
+
+    kinds
+}
+
+fn artifact_candidates(kind: &ProjectKind) -> Vec<(&'static str, RiskLevel, &'static str)> {
+    match kind {
+        ProjectKind::Cargo => vec![("target", RiskLevel::Low, "Cargo build output")],
+        ProjectKind::Node => vec![
+            ("node_modules", RiskLevel::Low, "Installed npm packages; restored by `npm install`"),
+            ("dist", RiskLevel::Medium, "Bundled build output"),
+            ("build", RiskLevel::Medium, "Build output"),
+        ],
+        ProjectKind::Python => vec![
+            (".venv", RiskLevel::Medium, "Python virtual environment"),
+            ("venv", RiskLevel::Medium, "Python virtual environment"),
+            ("build", RiskLevel::Low, "Python build output"),
+            ("dist", RiskLevel::Medium, "Built wheels and sdists"),
+        ],
+        ProjectKind::Gradle => vec![
+            ("build", RiskLevel::Low, "Gradle build output"),
+            (".gradle", RiskLevel::Safe, "Gradle project cache"),
+        ],
+        ProjectKind::CMake => vec![("build", RiskLevel::Low, "CMake build tree")],
+    }
+}
+
+/// Whether `path` is a build output directory of the project it sits in,
+/// i.e. one the build artifact finder reports on its own.
+pub fn is_project_artifact_dir(path: &Path) -> bool {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else { return false };
+    detect_project_kinds(parent).iter().any(|kind| {
+        artifact_candidates(kind).iter().any(|(candidate, _, _)| name == *candidate) && is_artifact_dir(kind, path)
+    })
+}
+
+/// Guards against deleting a same-named directory that holds sources.
+fn is_artifact_dir(kind: &ProjectKind, path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    match (kind, name.as_str()) {
+        (ProjectKind::Cargo, "target") => {
+            path.join(CACHEDIR_TAG_FILE).is_file() || path.join(".rustc_info.json").is_file()
+        }
+        (ProjectKind::Python, ".venv" | "venv") => path.join("pyvenv.cfg").is_file(),
+        (ProjectKind::CMake, "build") => path.join("CMakeCache.txt").is_file(),
+        _ => true,
+    }
+}
@@ -1,16 +1,34 @@
 // src-tauri/src/scanner/cache_scanner.rs
 use super::*;
 use crate::error::Result;
+use crate::paths::serde_path;
 use crate::state::AppState;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+/// Marker file defined by https://bford.info/cachedir/
+pub const CACHEDIR_TAG_FILE: &str = "CACHEDIR.TAG";
+const CACHEDIR_TAG_SIGNATURE: &[u8] = b"Signature: 8a477f597d28d172789f06886806bc55";
+/// How deep below the home directory to look for tagged cache directories.
+const CACHEDIR_TAG_MAX_DEPTH: usize = 6;
+/// How long a walk for tagged cache directories is reused.
+const CACHEDIR_TAG_REUSE: Duration = Duration::from_secs(10 * 60);
+
+static TAGGED_CACHE_DIRS: Mutex<Option<(Instant, Vec<CacheLocation>)>> = Mutex::new(None);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheLocation {
     pub name: String,
+    #[serde(with = "serde_path")]
     pub path: PathBuf,
     pub category: CacheCategory,
     pub safe_to_clean: bool,
+    /// Tool that created the cache, when known (e.g. from its CACHEDIR.TAG).
+    pub owner: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -47,18 +65,21 @@ impl CacheScanner {
         
         #[cfg(target_os = "linux")]
         {
-            if let Some(home) = dirs::home_dir() {
+            // Resolves $XDG_CACHE_HOME, falling back to ~/.cache
+            if let Some(cache_home) = dirs::cache_dir() {
                 locations.push(CacheLocation {
-                    name: "Chrome Cache".to_string(),
-                    path: home.join(".cache/google-chrome"),
-                    category: CacheCategory::Browser,
+                    name: "Thumbnails".to_string(),
+                    path: cache_home.join("thumbnails"),
+                    category: CacheCategory::Thumbnail,
                     safe_to_clean: true,
+                    owner: None,
                 });
                 locations.push(CacheLocation {
                     name: "User Cache".to_string(),
-                    path: home.join(".cache"),
+                    path: cache_home,
                     category: CacheCategory::System,
                     safe_to_clean: false,
+                    owner: None,
                 });
             }
         }
@@ -71,12 +92,7 @@ impl CacheScanner {
                     path: local.join("Temp"),
                     category: CacheCategory::System,
                     safe_to_clean: true,
-                });
-                locations.push(CacheLocation {
-                    name: "Chrome Cache".to_string(),
-                    path: local.join("Google/Chrome/User Data/Default/Cache"),
-                    category: CacheCategory::Browser,
-                    safe_to_clean: true,
+                    owner: None,
                 });
             }
             
@@ -85,6 +101,7 @@ impl CacheScanner {
                 path: PathBuf::from("C:\\Windows\\Temp"),
                 category: CacheCategory::System,
                 safe_to_clean: true,
+                owner: None,
             });
         }
 
@@ -96,16 +113,122 @@ impl CacheScanner {
                     path: home.join("Library/Caches"),
                     category: CacheCategory::System,
                     safe_to_clean: false,
+                    owner: None,
                 });
             }
         }
 
+        for profile in BrowserScanner::discover_profiles() {
+            for cache in profile.caches {
+                locations.push(CacheLocation {
+                    name: format!("{:?} {:?} ({})", profile.browser, cache.kind, profile.name),
+                    path: cache.path,
+                    category: CacheCategory::Browser,
+                    safe_to_clean: true,
+                    owner: Some(format!("{:?}", profile.browser)),
+                });
+            }
+        }
+
+        locations.extend(
+            PackageCacheScanner::default()
+                .get_locations()
+                .into_iter()
+                .map(|l| l.location),
+        );
+
+        for location in Self::find_tagged_cache_dirs() {
+            if !locations.iter().any(|l| l.path == location.path) {
+                locations.push(location);
+            }
+        }
+
         locations.into_iter().filter(|l| l.path.exists()).collect()
     }
 
+    /// Finds every directory carrying a valid CACHEDIR.TAG under the user's
+    /// home and cache directories. Tagged directories are not descended into,
+    /// and build output that the build artifact and cargo target scans
+    /// report (`target/`, `.gradle/`, ...) is left to them. The walk is
+    /// reused for `CACHEDIR_TAG_REUSE`.
+    pub fn find_tagged_cache_dirs() -> Vec<CacheLocation> {
+        let mut cached = TAGGED_CACHE_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((found_at, locations)) = cached.as_ref() {
+            if found_at.elapsed() < CACHEDIR_TAG_REUSE {
+                return locations.clone();
+            }
+        }
+
+        let locations = Self::walk_tagged_cache_dirs();
+        *cached = Some((Instant::now(), locations.clone()));
+        locations
+    }
+
+    fn walk_tagged_cache_dirs() -> Vec<CacheLocation> {
+        let mut roots = Vec::new();
+        let home = dirs::home_dir();
+        if let Some(cache_home) = dirs::cache_dir() {
+            // A relocated $XDG_CACHE_HOME outside the home directory needs its own walk
+            if !home.as_ref().map(|h| cache_home.starts_with(h)).unwrap_or(false) {
+                roots.push(cache_home);
+            }
+        }
+        roots.extend(home);
+
+        let local_rules = LocalRules::new();
+        let mut found: Vec<CacheLocation> = Vec::new();
+
+        for root in roots {
+            let mut walker = WalkDir::new(&root)
+                .follow_links(false)
+                .max_depth(CACHEDIR_TAG_MAX_DEPTH)
+                .into_iter();
+
+            while let Some(entry) = walker.next() {
+                let Ok(entry) = entry else { continue };
+                if !entry.file_type().is_dir() {
+                    continue;
+                }
+
+                let path = entry.path();
+                let name = entry.file_name();
+                if name == ".git" || name == "node_modules" || local_rules.is_ignored(path, true) {
+                    walker.skip_current_dir();
+                    continue;
+                }
+
+                if let Some(owner) = read_cachedir_tag(path) {
+                    walker.skip_current_dir();
+                    if is_project_artifact_dir(path) || path.join(".rustc_info.json").is_file() {
+                        continue;
+                    }
+                    if found.iter().any(|l| l.path == path) {
+                        continue;
+                    }
+                    found.push(CacheLocation {
+                        name: format!("{} cache ({})", owner, path.display()),
+                        path: path.to_path_buf(),
+                        category: CacheCategory::Application,
+                        safe_to_clean: true,
+                        owner: Some(owner),
+                    });
+                }
+            }
+        }
+
+        found
+    }
+
     pub fn scan(state: Option<Arc<AppState>>) -> Result<CacheScanResult> {
         let start = std::time::Instant::now();
         let locations = Self::get_cache_locations();
+        let all_paths: Vec<PathBuf> = locations.iter().map(|l| l.path.clone()).collect();
+        let browser_caches: Vec<PathBuf> = locations
+            .iter()
+            .filter(|l| l.category == CacheCategory::Browser)
+            .map(|l| l.path.clone())
+            .collect();
+        let rules = CleanupRule::with_browser_caches(&browser_caches);
         let mut results = Vec::new();
         let mut total_size = 0u64;
         let mut total_files = 0u64;
@@ -123,7 +246,19 @@ impl CacheScanner {
                 });
             }
 
-            let scanner = FileScanner::new(ScanOptions::default());
+            // Locations nested inside this one are reported on their own
+            let mut options = ScanOptions::default();
+            if read_cachedir_tag(&location.path).is_some() {
+                // Everything below a tagged directory is cache, whatever it is called
+                options.exclude_patterns.clear();
+            }
+            options.exclude_paths = all_paths
+                .iter()
+                .filter(|p| **p != location.path && p.starts_with(&location.path))
+                .cloned()
+                .collect();
+
+            let scanner = FileScanner::with_rules(options, rules.clone());
             if let Ok(scan_result) = scanner.scan(&[location.path.clone()], None) {
                 total_size += scan_result.total_size;
                 total_files += scan_result.total_count;
@@ -145,3 +280,42 @@ impl CacheScanner {
         })
     }
 }
+
+/// Returns the name of the tool that created `dir`'s CACHEDIR.TAG, or `None`
+/// if the directory has no tag or the signature does not match.
+pub fn read_cachedir_tag(dir: &Path) -> Option<String> {
+    let mut file = fs::File::open(dir.join(CACHEDIR_TAG_FILE)).ok()?;
+    let mut header = vec![0u8; 512];
+    let read = file.read(&mut header).ok()?;
+    header.truncate(read);
+
+    if !header.starts_with(CACHEDIR_TAG_SIGNATURE) {
+        return None;
+    }
+
+    // Tools conventionally follow the signature with
+    // "# This file is a cache directory tag created by <tool>."
+    let text = String::from_utf8_lossy(&header);
+    let owner = text
+        .lines()
+        .skip(1)
+        .find_map(|line| {
+            line.find("created by")
+                .map(|i| line[i + "created by".len()..].to_string())
+        })
+        .map(|tool| {
+            tool.trim()
+                .trim_end_matches('.')
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })
+        .filter(|tool| !tool.is_empty());
+
+    Some(owner.unwrap_or_else(|| {
+        dir.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    }))
+}
@@ -0,0 +1,332 @@
+// src-tauri/src/scanner/cargo_target.rs
+use super::*;
+use crate::error::Result;
+use crate::paths::serde_path;
+use crate::system::mounts;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetPruneReport {
+    #[serde(with = "serde_path")]
+    pub target_dir: PathBuf,
+    /// Toolchain recorded in `.rustc_info.json` for the most recent build.
+    pub toolchain: Option<String>,
+    pub toolchain_installed: bool,
+    pub units_total: u64,
+    pub units_stale: u64,
+    pub candidates: Vec<ScannedFile>,
+    pub reclaimable_size: u64,
+}
+
+/// One compilation unit as cargo tracks it in `.fingerprint/<name>-<hash>`.
+struct Unit {
+    name: String,
+    hash: String,
+    rustc: Option<u64>,
+    /// Newest mtime of its fingerprint files, or atime where the mount
+    /// records it: cargo reads but does not rewrite the fingerprints of
+    /// units that are still fresh.
+    last_used: Option<DateTime<Utc>>,
+}
+
+/// cargo-sweep style pruning of a cargo `target/` directory: removes the
+/// artifacts of individual units instead of the whole tree.
+pub struct CargoTargetPruner {
+    max_age_days: u32,
+    installed_toolchains: Vec<String>,
+}
+
+impl CargoTargetPruner {
+    pub fn new(max_age_days: u32) -> Self {
+        Self {
+            max_age_days,
+            installed_toolchains: installed_toolchains(),
+        }
+    }
+
+    pub fn analyze(&self, target_dir: &Path) -> Result<TargetPruneReport> {
+        let cutoff = Utc::now() - chrono::Duration::days(self.max_age_days as i64);
+        let toolchain = recorded_toolchain(target_dir);
+        let atime_reliable = mounts::mount_for(target_dir).map(|m| m.tracks_access_time()).unwrap_or(false);
+        let local_rules = LocalRules::new();
+        let toolchain_installed = match toolchain {
+            // Without rustup we have nothing to compare against
+            Some(ref name) => self.installed_toolchains.is_empty() || self.installed_toolchains.contains(name),
+            None => true,
+        };
+
+        let mut report = TargetPruneReport {
+            target_dir: target_dir.to_path_buf(),
+            toolchain: toolchain.clone(),
+            toolchain_installed,
+            units_total: 0,
+            units_stale: 0,
+            candidates: Vec::new(),
+            reclaimable_size: 0,
+        };
+
+        for profile_dir in find_profile_dirs(target_dir, &local_rules) {
+            let artifacts = unit_artifacts(&profile_dir);
+            let mut units = read_units(&profile_dir.join(".fingerprint"), atime_reliable);
+            if atime_reliable {
+                // rustc reads a dependency's rlib whenever a dependent is rebuilt
+                for (path, hash) in &artifacts {
+                    let Some(accessed) = fs::symlink_metadata(path).ok().and_then(|m| m.accessed().ok()) else { continue };
+                    let accessed = DateTime::<Utc>::from(accessed);
+                    for unit in units.iter_mut().filter(|u| &u.hash == hash) {
+                        unit.last_used = Some(unit.last_used.map_or(accessed, |t| t.max(accessed)));
+                    }
+                }
+            }
+            report.units_total += units.len() as u64;
+
+            // The rustc that produced the newest unit is the one currently in use
+            let active_rustc = units
+                .iter()
+                .filter(|u| u.last_used.is_some())
+                .max_by_key(|u| u.last_used)
+                .and_then(|u| u.rustc);
+            let project_active = units.iter().any(|u| u.last_used.map(|t| t > cutoff).unwrap_or(false));
+
+            let mut stale: HashMap<String, String> = HashMap::new();
+            for unit in &units {
+                let reason = if !toolchain_installed {
+                    Some(format!(
+                        "Built by toolchain {}, which is no longer installed",
+                        toolchain.as_deref().unwrap_or("unknown")
+                    ))
+                } else if unit.rustc.is_some() && active_rustc.is_some() && unit.rustc != active_rustc {
+                    Some("Built by a different rustc than the current toolchain".to_string())
+                } else if unit.last_used.map(|t| t < cutoff).unwrap_or(false) {
+                    if atime_reliable {
+                        Some(format!("Not used by any build in {} days", self.max_age_days))
+                    } else if is_superseded(unit, &units) {
+                        // Without access times an untouched unit may still be fresh;
+                        // only one that a newer build of its crate replaced is known unused
+                        Some(format!(
+                            "Replaced by a newer build of {} over {} days ago",
+                            unit.name, self.max_age_days
+                        ))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+
+                if let Some(reason) = reason {
+                    stale.insert(unit.hash.clone(), reason);
+                }
+            }
+            report.units_stale += stale.len() as u64;
+
+            for (path, hash) in &artifacts {
+                if local_rules.is_ignored(path, path.is_dir()) {
+                    continue;
+                }
+                if let Some(reason) = stale.get(hash) {
+                    report.candidates.push(artifact_candidate(path, reason));
+                }
+            }
+
+            // Incremental caches are only worth keeping while the project is being built
+            if !project_active || !toolchain_installed {
+                for session in read_dir_paths(&profile_dir.join("incremental")) {
+                    if local_rules.is_ignored(&session, true) {
+                        continue;
+                    }
+                    let usage = dir_usage(&session);
+                    let idle = usage.last_modified.map(|t| t < cutoff).unwrap_or(true);
+                    if idle || !toolchain_installed {
+                        report.candidates.push(ScannedFile::from_directory(
+                            &session,
+                            &usage,
+                            FileCategory::Cache,
+                            RiskLevel::Safe,
+                            Some("Incremental compilation cache of an inactive project".to_string()),
+                        ));
+                    }
+                }
+            }
+        }
+
+        report.candidates.sort_by(|a, b| b.size.cmp(&a.size));
+        report.reclaimable_size = report.candidates.iter().map(|c| c.size).sum();
+        Ok(report)
+    }
+}
+
+/// Resolves each path to the cargo target directories it holds: either the
+/// path itself or the `target/` of every cargo project found below it.
+pub fn find_target_dirs(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut targets = Vec::new();
+
+    for path in paths {
+        if path.join(".rustc_info.json").is_file() {
+            targets.push(path.clone());
+            continue;
+        }
+
+        let Ok(found) = BuildArtifactFinder::default().find(&[path.clone()], None) else { continue };
+        for project in found.projects.iter().filter(|p| p.kinds.contains(&ProjectKind::Cargo)) {
+            let target = project.root.join("target");
+            if target.is_dir() && !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+
+    targets
+}
+
+/// Toolchains installed under `$RUSTUP_HOME/toolchains` (default `~/.rustup`).
+pub fn installed_toolchains() -> Vec<String> {
+    let rustup_home = std::env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".rustup")));
+
+    rustup_home
+        .map(|home| {
+            read_dir_paths(&home.join("toolchains"))
+                .into_iter()
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the sysroot cargo cached in `target/.rustc_info.json` and returns the
+/// rustup toolchain name it belongs to.
+fn recorded_toolchain(target_dir: &Path) -> Option<String> {
+    let data = fs::read_to_string(target_dir.join(".rustc_info.json")).ok()?;
+    let info: serde_json::Value = serde_json::from_str(&data).ok()?;
+
+    info.get("outputs")?
+        .as_object()?
+        .values()
+        .filter_map(|output| output.get("stdout").and_then(|s| s.as_str()))
+        .flat_map(|stdout| stdout.lines())
+        .find_map(|line| {
+            let path = Path::new(line.trim());
+            let mut components = path.components().rev();
+            let name = components.next()?;
+            let parent = components.next()?;
+            (parent.as_os_str() == "toolchains").then(|| name.as_os_str().to_string_lossy().to_string())
+        })
+}
+
+/// Profile directories (`debug`, `release`, `<triple>/debug`, ...) are the
+/// ones that contain a `.fingerprint` directory. `.sweepignore`d ones are
+/// left out.
+fn find_profile_dirs(target_dir: &Path, local_rules: &LocalRules) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(target_dir)
+        .min_depth(1)
+        .max_depth(2)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| !(e.file_type().is_dir() && local_rules.is_ignored(e.path(), true)))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir() && e.path().join(".fingerprint").is_dir())
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+fn read_units(fingerprint_dir: &Path, atime_reliable: bool) -> Vec<Unit> {
+    read_dir_paths(fingerprint_dir)
+        .into_iter()
+        .filter_map(|dir| {
+            let dir_name = dir.file_name()?.to_str()?;
+            let hash = unit_hash(dir_name)?;
+            let name = dir_name[..dir_name.len() - hash.len() - 1].to_string();
+            let mut rustc = None;
+            let mut last_used: Option<DateTime<Utc>> = None;
+
+            for file in read_dir_paths(&dir) {
+                let Ok(metadata) = fs::metadata(&file) else { continue };
+                let accessed = if atime_reliable { metadata.accessed().ok() } else { None };
+                for time in metadata.modified().ok().into_iter().chain(accessed) {
+                    let time = DateTime::<Utc>::from(time);
+                    last_used = Some(last_used.map_or(time, |t| t.max(time)));
+                }
+                if rustc.is_none() && file.extension().map(|e| e == "json").unwrap_or(false) {
+                    rustc = fs::read_to_string(&file)
+                        .ok()
+                        .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+                        .and_then(|v| v.get("rustc").and_then(|r| r.as_u64()));
+                }
+            }
+
+            Some(Unit { name, hash, rustc, last_used })
+        })
+        .collect()
+}
+
+/// Whether a newer unit of the same crate exists, e.g. after a dependency
+/// was upgraded or its features changed.
+fn is_superseded(unit: &Unit, units: &[Unit]) -> bool {
+    units
+        .iter()
+        .any(|other| other.name == unit.name && other.hash != unit.hash && other.last_used > unit.last_used)
+}
+
+/// Every path in a profile directory that belongs to a unit, keyed by the
+/// unit's metadata hash.
+fn unit_artifacts(profile_dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut artifacts = Vec::new();
+
+    for sub in [".fingerprint", "build", "deps", "examples"] {
+        for path in read_dir_paths(&profile_dir.join(sub)) {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            // deps/libfoo-0123456789abcdef.rlib -> foo-0123456789abcdef
+            let stem = name.split('.').next().unwrap_or(name);
+            if let Some(hash) = unit_hash(stem) {
+                artifacts.push((path, hash));
+            }
+        }
+    }
+
+    artifacts
+}
+
+/// Extracts the 16-hex-digit metadata hash cargo appends to unit names.
+fn unit_hash(name: &str) -> Option<String> {
+    let (_, hash) = name.rsplit_once('-')?;
+    (hash.len() == 16 && hash.chars().all(|c| c.is_ascii_hexdigit())).then(|| hash.to_string())
+}
+
+fn artifact_candidate(path: &Path, reason: &str) -> ScannedFile {
+    if path.is_dir() {
+        let usage = dir_usage(path);
+        return ScannedFile::from_directory(path, &usage, FileCategory::Cache, RiskLevel::Low, Some(reason.to_string()));
+    }
+
+    let metadata = fs::symlink_metadata(path).ok();
+    ScannedFile {
+        path: path.to_path_buf(),
+        size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+        modified: metadata
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(Utc::now),
+        created: None,
+        accessed: metadata.as_ref().and_then(|m| m.accessed().ok()).map(DateTime::<Utc>::from),
+        file_type: FileType::File,
+        category: FileCategory::Cache,
+        hash: None,
+        is_hidden: false,
+        is_system: false,
+        risk_level: RiskLevel::Low,
+        description: Some(reason.to_string()),
+        identity: metadata.as_ref().and_then(FileIdentity::of),
+    }
+}
+
+fn read_dir_paths(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,204 @@
+// src-tauri/src/scanner/downloads.rs
+use super::*;
+use crate::cleanup::read_recent_entries;
+use crate::error::{CleanerError, Result};
+use crate::paths::{path_from_bytes, path_to_bytes, serde_path};
+use crate::state::AppState;
+use crate::system::mounts;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Multi-part extensions first so `foo.tar.gz` strips to `foo`, not `foo.tar`.
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+    ".tar.gz", ".tar.bz2", ".tar.xz", ".tar.zst", ".tgz", ".tbz2", ".txz", ".tar", ".zip", ".7z", ".rar",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadSignals {
+    /// Listed in recently-used.xbel, i.e. opened through a GTK application.
+    pub in_recent_files: bool,
+    /// atime later than mtime. `None` when the mount is `noatime` and atime
+    /// carries no information.
+    pub accessed_after_download: Option<bool>,
+    /// Directory next to an archive that holds its extracted contents, or the
+    /// dpkg file list of an installed `.deb`.
+    #[serde(with = "serde_path::option")]
+    pub unpacked_as: Option<PathBuf>,
+}
+
+impl DownloadSignals {
+    pub fn never_opened(&self) -> bool {
+        !self.in_recent_files && self.accessed_after_download == Some(false) && self.unpacked_as.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadCandidate {
+    pub file: ScannedFile,
+    pub signals: DownloadSignals,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadScanResult {
+    #[serde(with = "serde_path")]
+    pub directory: PathBuf,
+    pub candidates: Vec<DownloadCandidate>,
+    pub total_size: u64,
+    /// False when the downloads folder is on a `noatime` mount.
+    pub atime_reliable: bool,
+}
+
+pub struct DownloadAnalyzer {
+    min_age_days: u32,
+}
+
+impl DownloadAnalyzer {
+    pub fn new(min_age_days: u32) -> Self {
+        Self { min_age_days }
+    }
+
+    pub fn analyze(&self, state: Option<Arc<AppState>>) -> Result<Option<DownloadScanResult>> {
+        match dirs::download_dir() {
+            Some(dir) if dir.is_dir() => self.analyze_dir(&dir, state).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn analyze_dir(&self, dir: &Path, state: Option<Arc<AppState>>) -> Result<DownloadScanResult> {
+        let atime_reliable = mounts::mount_for(dir).map(|m| m.tracks_access_time()).unwrap_or(true);
+        let recent: HashSet<PathBuf> = read_recent_entries().into_iter().filter_map(|e| e.path).collect();
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(self.min_age_days as i64);
+        let local_rules = LocalRules::new();
+        let mut candidates = Vec::new();
+
+        let entries = fs::read_dir(dir)?.filter_map(|e| e.ok());
+        for entry in entries {
+            if let Some(ref s) = state {
+                if s.is_cancelled() {
+                    return Err(CleanerError::Cancelled);
+                }
+            }
+
+            let path = entry.path();
+            let Ok(metadata) = fs::symlink_metadata(&path) else { continue };
+            if !metadata.is_file() || local_rules.is_ignored(&path, false) {
+                continue;
+            }
+            // Browsers write partial downloads next to the final file
+            if is_partial_download(&path) {
+                continue;
+            }
+
+            let modified = metadata.modified().ok().map(chrono::DateTime::<chrono::Utc>::from);
+            if modified.map(|m| m > cutoff).unwrap_or(true) {
+                continue;
+            }
+            let accessed = metadata.accessed().ok().map(chrono::DateTime::<chrono::Utc>::from);
+
+            let signals = DownloadSignals {
+                in_recent_files: recent.contains(&path),
+                accessed_after_download: match (atime_reliable, accessed, modified) {
+                    (true, Some(a), Some(m)) => Some(a > m),
+                    _ => None,
+                },
+                unpacked_as: unpacked_location(&path),
+            };
+
+            let (risk_level, description) = if signals.never_opened() {
+                (RiskLevel::Low, "Downloaded but never opened")
+            } else if signals.unpacked_as.is_some() {
+                (RiskLevel::Low, "Contents already extracted or installed")
+            } else {
+                (RiskLevel::Medium, "Old download that has been opened")
+            };
+
+            candidates.push(DownloadCandidate {
+                file: ScannedFile {
+                    path: path.clone(),
+                    size: metadata.len(),
+                    modified: modified.unwrap_or_else(chrono::Utc::now),
+                    created: metadata.created().ok().map(chrono::DateTime::<chrono::Utc>::from),
+                    accessed,
+                    file_type: FileType::File,
+                    category: FileCategory::Download,
+                    hash: None,
+                    is_hidden: is_hidden_file(&path),
+                    is_system: false,
+                    risk_level,
+                    description: Some(description.to_string()),
+                    identity: FileIdentity::of(&metadata),
+                },
+                signals,
+            });
+        }
+
+        // Never-opened first, then by size
+        candidates.sort_by(|a, b| {
+            b.signals
+                .never_opened()
+                .cmp(&a.signals.never_opened())
+                .then(b.file.size.cmp(&a.file.size))
+        });
+
+        Ok(DownloadScanResult {
+            directory: dir.to_path_buf(),
+            total_size: candidates.iter().map(|c| c.file.size).sum(),
+            candidates,
+            atime_reliable,
+        })
+    }
+}
+
+impl Default for DownloadAnalyzer {
+    fn default() -> Self {
+        Self::new(30)
+    }
+}
+
+fn is_partial_download(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    name.ends_with(".part") || name.ends_with(".crdownload") || name.ends_with(".download")
+}
+
+/// Where the contents of an archive or installer already live, if anywhere.
+fn unpacked_location(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy().to_string();
+    let lower = name.to_lowercase();
+
+    if let Some(ext) = ARCHIVE_EXTENSIONS.iter().find(|e| lower.ends_with(*e)) {
+        // The extension is ASCII, so cutting it off the raw bytes keeps a
+        // name that is not UTF-8 intact
+        let raw = path_to_bytes(Path::new(path.file_name()?));
+        let stem = &raw[..raw.len() - ext.len()];
+        // A file named just `.zip` would point at its own directory
+        if stem.is_empty() {
+            return None;
+        }
+        let sibling = path.with_file_name(path_from_bytes(stem.to_vec()));
+        return sibling.is_dir().then_some(sibling);
+    }
+
+    if lower.ends_with(".deb") {
+        // Debian naming: <package>_<version>_<arch>.deb
+        let package = name.split('_').next()?;
+        // Multi-arch packages are listed as <package>:<arch>.list
+        return fs::read_dir("/var/lib/dpkg/info")
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| {
+                let file = p.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                file == format!("{}.list", package)
+                    || (file.starts_with(&format!("{}:", package)) && file.ends_with(".list"))
+            });
+    }
+
+    if lower.ends_with(".appimage") {
+        let extracted = path.with_file_name("squashfs-root");
+        return extracted.is_dir().then_some(extracted);
+    }
+
+    None
+}
@@ -1,6 +1,7 @@
 // src-tauri/src/scanner/duplicate_detector.rs
 use super::*;
 use crate::error::{CleanerError, Result};
+use crate::paths::serde_path;
 use crate::state::AppState;
 use blake3::Hasher as Blake3Hasher;
 use std::collections::HashMap;
@@ -13,6 +14,7 @@ use std::sync::Arc;
 pub struct DuplicateGroup {
     pub hash: String,
     pub size: u64,
+    #[serde(with = "serde_path::vec")]
     pub files: Vec<PathBuf>,
     pub total_wasted_space: u64,
 }
@@ -3,7 +3,7 @@ use super::*;
 use crate::error::{CleanerError, Result};
 use crate::state::AppState;
 use chrono::{DateTime, Utc};
-use glob::Pattern;
+use globset::{Glob, GlobMatcher};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -13,33 +13,26 @@ use walkdir::WalkDir;
 
 pub struct FileScanner {
     options: ScanOptions,
-    exclude_patterns: Vec<Pattern>,
-    include_patterns: Vec<Pattern>,
+    exclude_patterns: Vec<GlobMatcher>,
+    include_patterns: Vec<GlobMatcher>,
     rule_engine: RuleEngine,
 }
 
 impl FileScanner {
     pub fn new(options: ScanOptions) -> Self {
-        let exclude_patterns: Vec<Pattern> = options
-            .exclude_patterns
-            .iter()
-            .filter_map(|p| Pattern::new(p).ok())
-            .collect();
-            
-        let include_patterns: Vec<Pattern> = options
-            .include_patterns
-            .iter()
-            .filter_map(|p| Pattern::new(p).ok())
-            .collect();
+        Self::with_rules(options, CleanupRule::default_rules())
+    }
 
-        // Initialize RuleEngine with default rules
-        let rule_engine = RuleEngine::new(CleanupRule::default_rules());
+    /// For scanners created in a loop, which should build `rules` once.
+    pub fn with_rules(options: ScanOptions, rules: Vec<CleanupRule>) -> Self {
+        let exclude_patterns = compile_patterns(&options.exclude_patterns);
+        let include_patterns = compile_patterns(&options.include_patterns);
 
         Self {
             options,
             exclude_patterns,
             include_patterns,
-            rule_engine,
+            rule_engine: RuleEngine::new(rules),
         }
     }
 
@@ -50,6 +43,8 @@ impl FileScanner {
         let mut all_files = Vec::new();
         let mut errors = Vec::new();
 
+        let local_rules = LocalRules::new();
+
         for base_path in paths {
             if let Some(ref s) = state {
                 if s.is_cancelled() {
@@ -60,7 +55,8 @@ impl FileScanner {
             let walker = WalkDir::new(base_path)
                 .follow_links(self.options.follow_symlinks)
                 .max_depth(self.options.max_depth.unwrap_or(usize::MAX))
-                .into_iter();
+                .into_iter()
+                .filter_entry(|e| !local_rules.is_ignored(e.path(), e.file_type().is_dir()));
 
             for entry_result in walker {
                 if let Some(ref s) = state {
@@ -81,7 +77,8 @@ impl FileScanner {
                             continue;
                         }
 
-                        if let Some(scanned) = self.scan_file(path) {
+                        let overrides = local_rules.overrides_for(path);
+                        if let Some(scanned) = self.scan_file(path, &overrides) {
                             if self.passes_filters(&scanned) {
                                 files_scanned.fetch_add(1, Ordering::Relaxed);
                                 bytes_scanned.fetch_add(scanned.size, Ordering::Relaxed);
@@ -124,7 +121,7 @@ impl FileScanner {
         })
     }
 
-    fn scan_file(&self, path: &Path) -> Option<ScannedFile> {
+    fn scan_file(&self, path: &Path, overrides: &ResolvedOverrides) -> Option<ScannedFile> {
         let metadata = fs::metadata(path).ok()?;
         
         let modified = metadata
@@ -145,7 +142,9 @@ impl FileScanner {
         };
 
         // Use RuleEngine for categorization and risk assessment
-        let (category, risk_level, description) = self.rule_engine.categorize(path, &metadata);
+        let (category, risk_level, description) =
+            self.rule_engine
+                .categorize_excluding(path, &metadata, &overrides.disable_rules);
         
         // Fallback categorization if RuleEngine returns Unknown (to keep user's original logic as safety net)
         let final_category = if category == FileCategory::Unknown {
@@ -154,6 +153,18 @@ impl FileScanner {
             category
         };
 
+        // Directory-level .systemsweep.toml overrides take precedence over global
+        // rules, except that a risk level can only be raised
+        let final_category = overrides.category.clone().unwrap_or(final_category);
+        let risk_level = match overrides.risk_level.clone() {
+            Some(declared) => risk_level.max(declared),
+            None => risk_level,
+        };
+
+        if overrides.skip_categories.contains(&final_category) {
+            return None;
+        }
+
         let is_hidden = is_hidden_file(path);
         
         #[cfg(windows)]
@@ -178,17 +189,19 @@ impl FileScanner {
             is_system,
             risk_level,
             description,
+            identity: FileIdentity::of_path(path),
         })
     }
 
+    // globset matches the raw bytes of the path, so a name that is not
+    // UTF-8 is still excluded by `*.tmp` or its parent directory's pattern
     fn should_exclude(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-        self.exclude_patterns.iter().any(|p| p.matches(&path_str))
+        self.options.exclude_paths.iter().any(|p| path.starts_with(p))
+            || self.exclude_patterns.iter().any(|p| p.is_match(path))
     }
 
     fn should_include(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy();
-        self.include_patterns.iter().any(|p| p.matches(&path_str))
+        self.include_patterns.iter().any(|p| p.is_match(path))
     }
 
     fn passes_filters(&self, file: &ScannedFile) -> bool {
@@ -231,6 +244,14 @@ impl FileScanner {
     }
 }
 
+fn compile_patterns(patterns: &[String]) -> Vec<GlobMatcher> {
+    patterns
+        .iter()
+        .filter_map(|p| Glob::new(p).ok())
+        .map(|g| g.compile_matcher())
+        .collect()
+}
+
 pub fn categorize_file_fallback(path: &Path) -> FileCategory {
     let extension = path.extension()
         .and_then(|e| e.to_str())
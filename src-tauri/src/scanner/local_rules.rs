@@ -0,0 +1,263 @@
+// src-tauri/src/scanner/local_rules.rs
+use super::*;
+use ignore::gitignore::Gitignore;
+use ignore::Match;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Gitignore-syntax file whose patterns are scoped to the directory it lives in.
+pub const SWEEPIGNORE_FILE: &str = ".sweepignore";
+/// Per-directory rule overrides that apply to the whole subtree.
+pub const LOCAL_CONFIG_FILE: &str = ".systemsweep.toml";
+
+/// Contents of a `.systemsweep.toml` file.
+///
+/// ```toml
+/// protect = false
+/// disable_rules = ["log_files"]
+/// skip_categories = ["Log"]
+/// category = "Cache"
+/// risk_level = "High"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DirectoryOverrides {
+    /// Never scan this directory or anything below it.
+    pub protect: bool,
+    /// Rule ids that must not match anything in this subtree.
+    pub disable_rules: Vec<String>,
+    /// Files that end up in one of these categories are never reported.
+    pub skip_categories: Vec<FileCategory>,
+    /// Treat every file in this subtree as this category.
+    pub category: Option<FileCategory>,
+    /// Minimum risk of every file in this subtree; it never lowers a rule's.
+    pub risk_level: Option<RiskLevel>,
+}
+
+/// Overrides in effect for one path after merging every ancestor's config.
+/// The nearest directory wins for `category` and `risk_level`; lists accumulate.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedOverrides {
+    pub disable_rules: Vec<String>,
+    pub skip_categories: Vec<FileCategory>,
+    pub category: Option<FileCategory>,
+    pub risk_level: Option<RiskLevel>,
+}
+
+#[derive(Default)]
+struct DirectoryRules {
+    ignore: Option<Gitignore>,
+    overrides: Option<DirectoryOverrides>,
+}
+
+/// Lazily loads and caches `.sweepignore` and `.systemsweep.toml` files for
+/// every directory a scan touches.
+pub struct LocalRules {
+    cache: Mutex<HashMap<PathBuf, Arc<DirectoryRules>>>,
+}
+
+impl LocalRules {
+    pub fn new() -> Self {
+        Self {
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns true if `path` is excluded by a `.sweepignore` in any ancestor
+    /// directory or lives inside a protected subtree.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if let Some(name) = path.file_name() {
+            if name == SWEEPIGNORE_FILE || name == LOCAL_CONFIG_FILE {
+                return true;
+            }
+        }
+
+        let protected_from = if is_dir { 0 } else { 1 };
+        for dir in path.ancestors().skip(protected_from) {
+            let rules = self.rules_for_dir(dir);
+            if rules.overrides.as_ref().map(|o| o.protect).unwrap_or(false) {
+                return true;
+            }
+        }
+
+        // The deepest .sweepignore with an opinion decides, as with git. An
+        // ignored directory also covers everything below it.
+        for dir in path.ancestors().skip(1) {
+            let rules = self.rules_for_dir(dir);
+            if let Some(ref gitignore) = rules.ignore {
+                match gitignore.matched_path_or_any_parents(path, is_dir) {
+                    Match::Ignore(_) => return true,
+                    Match::Whitelist(_) => return false,
+                    Match::None => {}
+                }
+            }
+        }
+
+        false
+    }
+
+    pub fn overrides_for(&self, path: &Path) -> ResolvedOverrides {
+        let mut resolved = ResolvedOverrides::default();
+
+        for dir in path.ancestors().skip(1) {
+            let rules = self.rules_for_dir(dir);
+            let Some(ref overrides) = rules.overrides else {
+                continue;
+            };
+
+            resolved.disable_rules.extend(overrides.disable_rules.iter().cloned());
+            resolved.skip_categories.extend(overrides.skip_categories.iter().cloned());
+            if resolved.category.is_none() {
+                resolved.category = overrides.category.clone();
+            }
+            if resolved.risk_level.is_none() {
+                resolved.risk_level = overrides.risk_level.clone();
+            }
+        }
+
+        resolved
+    }
+
+    fn rules_for_dir(&self, dir: &Path) -> Arc<DirectoryRules> {
+        if let Some(rules) = self.cache.lock().unwrap().get(dir) {
+            return rules.clone();
+        }
+
+        let rules = Arc::new(load_directory_rules(dir));
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(dir.to_path_buf(), rules.clone());
+        rules
+    }
+}
+
+impl Default for LocalRules {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn load_directory_rules(dir: &Path) -> DirectoryRules {
+    let mut rules = DirectoryRules::default();
+
+    let ignore_path = dir.join(SWEEPIGNORE_FILE);
+    if ignore_path.is_file() {
+        let (gitignore, err) = Gitignore::new(&ignore_path);
+        if let Some(e) = err {
+            tracing::warn!("Problem in {:?}: {}", ignore_path, e);
+        }
+        if !gitignore.is_empty() {
+            rules.ignore = Some(gitignore);
+        }
+    }
+
+    let config_path = dir.join(LOCAL_CONFIG_FILE);
+    if config_path.is_file() {
+        match fs::read_to_string(&config_path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| toml::from_str::<DirectoryOverrides>(&s).map_err(|e| e.to_string()))
+        {
+            Ok(overrides) => rules.overrides = Some(overrides),
+            Err(e) => tracing::warn!("Ignoring invalid {:?}: {}", config_path, e),
+        }
+    }
+
+    rules
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("systemsweep-local-rules-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn negated_patterns_keep_files() {
+        let root = temp_dir("negation");
+        fs::write(root.join(SWEEPIGNORE_FILE), "*.log\n!keep.log\n").unwrap();
+
+        let rules = LocalRules::new();
+        assert!(rules.is_ignored(&root.join("app.log"), false));
+        assert!(!rules.is_ignored(&root.join("keep.log"), false));
+        assert!(!rules.is_ignored(&root.join("app.tmp"), false));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn anchored_patterns_only_match_next_to_the_file() {
+        let root = temp_dir("anchored");
+        fs::create_dir_all(root.join("sub/build")).unwrap();
+        fs::write(root.join(SWEEPIGNORE_FILE), "/build\n").unwrap();
+
+        let rules = LocalRules::new();
+        assert!(rules.is_ignored(&root.join("build"), true));
+        assert!(rules.is_ignored(&root.join("build/out.o"), false));
+        assert!(!rules.is_ignored(&root.join("sub/build"), true));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn nearest_sweepignore_wins() {
+        let root = temp_dir("nearest-ignore");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join(SWEEPIGNORE_FILE), "*.tmp\n").unwrap();
+        fs::write(root.join("sub").join(SWEEPIGNORE_FILE), "!*.tmp\n").unwrap();
+
+        let rules = LocalRules::new();
+        assert!(rules.is_ignored(&root.join("a.tmp"), false));
+        assert!(!rules.is_ignored(&root.join("sub/a.tmp"), false));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn nearest_config_wins_and_lists_accumulate() {
+        let root = temp_dir("nearest-config");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(
+            root.join(LOCAL_CONFIG_FILE),
+            "disable_rules = [\"log_files\"]\nskip_categories = [\"Log\"]\ncategory = \"Log\"\nrisk_level = \"High\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("sub").join(LOCAL_CONFIG_FILE),
+            "disable_rules = [\"temp_files\"]\nskip_categories = [\"Cache\"]\nrisk_level = \"Low\"\n",
+        )
+        .unwrap();
+
+        let rules = LocalRules::new();
+        let top = rules.overrides_for(&root.join("a.txt"));
+        assert_eq!(top.risk_level, Some(RiskLevel::High));
+        assert_eq!(top.skip_categories, vec![FileCategory::Log]);
+
+        let nested = rules.overrides_for(&root.join("sub/a.txt"));
+        assert_eq!(nested.risk_level, Some(RiskLevel::Low));
+        assert_eq!(nested.category, Some(FileCategory::Log));
+        assert!(nested.disable_rules.contains(&"log_files".to_string()));
+        assert!(nested.disable_rules.contains(&"temp_files".to_string()));
+        assert!(nested.skip_categories.contains(&FileCategory::Log));
+        assert!(nested.skip_categories.contains(&FileCategory::Cache));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn protected_subtrees_and_rule_files_are_ignored() {
+        let root = temp_dir("protect");
+        fs::create_dir_all(root.join("keep/deep")).unwrap();
+        fs::write(root.join("keep").join(LOCAL_CONFIG_FILE), "protect = true\n").unwrap();
+
+        let rules = LocalRules::new();
+        assert!(rules.is_ignored(&root.join("keep"), true));
+        assert!(rules.is_ignored(&root.join("keep/deep/a.log"), false));
+        assert!(rules.is_ignored(&root.join("keep").join(LOCAL_CONFIG_FILE), false));
+        assert!(!rules.is_ignored(&root.join("other.log"), false));
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -4,18 +4,32 @@ pub mod duplicate_detector;
 pub mod large_file_finder;
 pub mod cache_scanner;
 pub mod rules;
+pub mod local_rules;
+pub mod package_cache;
+pub mod build_artifacts;
+pub mod cargo_target;
+pub mod browser;
+pub mod downloads;
 
 pub use file_scanner::*;
 pub use duplicate_detector::*;
 pub use large_file_finder::*;
 pub use cache_scanner::*;
 pub use rules::*;
+pub use local_rules::*;
+pub use package_cache::*;
+pub use build_artifacts::*;
+pub use cargo_target::*;
+pub use browser::*;
+pub use downloads::*;
 
+use crate::paths::serde_path;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScannedFile {
+    #[serde(with = "serde_path")]
     pub path: PathBuf,
     pub size: u64,
     pub modified: chrono::DateTime<chrono::Utc>,
@@ -28,6 +42,40 @@ pub struct ScannedFile {
     pub is_system: bool,
     pub risk_level: RiskLevel,
     pub description: Option<String>,
+    /// Device and inode when scanned; deleting checks the path still
+    /// refers to the same file.
+    #[serde(default)]
+    pub identity: Option<FileIdentity>,
+}
+
+/// Device and inode of a path itself (symlinks are not followed).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct FileIdentity {
+    pub device: u64,
+    pub inode: u64,
+}
+
+impl FileIdentity {
+    /// `None` where the platform has no inode numbers.
+    pub fn of(metadata: &std::fs::Metadata) -> Option<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            Some(Self {
+                device: metadata.dev(),
+                inode: metadata.ino(),
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = metadata;
+            None
+        }
+    }
+
+    pub fn of_path(path: &std::path::Path) -> Option<Self> {
+        std::fs::symlink_metadata(path).ok().and_then(|m| Self::of(&m))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -54,7 +102,8 @@ pub enum FileCategory {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Ordered from least to most risky.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RiskLevel {
     Safe,
     Low,
@@ -73,6 +122,10 @@ pub struct ScanOptions {
     pub max_age_days: Option<u32>,
     pub include_patterns: Vec<String>,
     pub exclude_patterns: Vec<String>,
+    /// Skipped with everything below them; compared byte for byte, so it
+    /// also works for paths a glob pattern cannot spell.
+    #[serde(default, with = "serde_path::vec")]
+    pub exclude_paths: Vec<PathBuf>,
     pub categories: Vec<FileCategory>,
 }
 
@@ -92,6 +145,7 @@ impl Default for ScanOptions {
                 "**/.git/**".to_string(),
                 "**/target/**".to_string(),
             ],
+            exclude_paths: vec![],
             categories: vec![],
         }
     }
@@ -108,6 +162,69 @@ pub struct ScanResult {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanError {
+    #[serde(with = "serde_path")]
     pub path: PathBuf,
     pub error: String,
 }
+
+/// Aggregate size and activity of everything below a directory.
+#[derive(Debug, Clone, Default)]
+pub struct DirUsage {
+    pub size: u64,
+    pub file_count: u64,
+    pub last_modified: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_accessed: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Walks `path` without following symlinks and sums up its files.
+pub fn dir_usage(path: &std::path::Path) -> DirUsage {
+    let mut usage = DirUsage::default();
+
+    for entry in walkdir::WalkDir::new(path).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+        let Ok(metadata) = entry.metadata() else { continue };
+        if metadata.is_dir() {
+            continue;
+        }
+
+        usage.size += metadata.len();
+        usage.file_count += 1;
+
+        if let Ok(modified) = metadata.modified() {
+            let modified = chrono::DateTime::<chrono::Utc>::from(modified);
+            usage.last_modified = Some(usage.last_modified.map_or(modified, |m| m.max(modified)));
+        }
+        if let Ok(accessed) = metadata.accessed() {
+            let accessed = chrono::DateTime::<chrono::Utc>::from(accessed);
+            usage.last_accessed = Some(usage.last_accessed.map_or(accessed, |a| a.max(accessed)));
+        }
+    }
+
+    usage
+}
+
+impl ScannedFile {
+    /// Describes a whole directory as a single cleanup candidate.
+    pub fn from_directory(
+        path: &std::path::Path,
+        usage: &DirUsage,
+        category: FileCategory,
+        risk_level: RiskLevel,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            size: usage.size,
+            modified: usage.last_modified.unwrap_or_else(chrono::Utc::now),
+            created: None,
+            accessed: usage.last_accessed,
+            file_type: FileType::Directory,
+            category,
+            hash: None,
+            is_hidden: is_hidden_file(path),
+            is_system: false,
+            risk_level,
+            description,
+            identity: FileIdentity::of_path(path),
+        }
+    }
+}
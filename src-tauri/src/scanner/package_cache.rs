@@ -0,0 +1,485 @@
+// src-tauri/src/scanner/package_cache.rs
+use super::*;
+use crate::error::{CleanerError, Result};
+use crate::state::AppState;
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+/// Artifacts in maven/gradle repositories untouched for this long are reported.
+pub const DEFAULT_PACKAGE_IDLE_DAYS: u32 = 90;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PackageEcosystem {
+    Cargo,
+    Npm,
+    Pip,
+    Go,
+    Maven,
+    Gradle,
+    Yarn,
+}
+
+/// How the entries of a package cache are laid out on disk.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CacheLayout {
+    /// Loose files; every file is its own candidate.
+    Files,
+    /// Each immediate subdirectory is one rebuildable unit.
+    Subdirectories,
+    /// Maven `group/artifact/version` directories, recognized by their `.pom`.
+    MavenArtifacts,
+    /// Gradle `modules-2/files-2.1/group/artifact/version` directories.
+    GradleArtifacts,
+    /// Go `module@version` directories.
+    GoModules,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageCacheLocation {
+    pub ecosystem: PackageEcosystem,
+    pub location: CacheLocation,
+    pub layout: CacheLayout,
+    pub risk_level: RiskLevel,
+    /// Can the tool recreate this without hitting the network?
+    pub rebuildable: bool,
+    /// Only report entries that haven't been read or written for this long.
+    pub min_idle_days: Option<u32>,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageCacheResult {
+    pub ecosystem: PackageEcosystem,
+    pub location: CacheLocation,
+    pub risk_level: RiskLevel,
+    pub total_size: u64,
+    pub reclaimable_size: u64,
+    pub entries: Vec<ScannedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageCacheScanResult {
+    pub caches: Vec<PackageCacheResult>,
+    pub total_size: u64,
+    pub reclaimable_size: u64,
+    pub scan_duration_ms: u64,
+}
+
+pub struct PackageCacheScanner {
+    idle_days: u32,
+}
+
+impl PackageCacheScanner {
+    pub fn new(idle_days: u32) -> Self {
+        Self { idle_days }
+    }
+
+    /// Every package-manager cache present on this machine, resolving the
+    /// tools' own environment overrides (CARGO_HOME, GOMODCACHE, ...).
+    pub fn get_locations(&self) -> Vec<PackageCacheLocation> {
+        let mut locations = Vec::new();
+        let home = dirs::home_dir();
+        let cache_home = dirs::cache_dir();
+
+        // Cargo
+        if let Some(cargo_home) = env_dir("CARGO_HOME").or_else(|| home.as_ref().map(|h| h.join(".cargo"))) {
+            locations.push(package_location(
+                PackageEcosystem::Cargo,
+                "Cargo registry sources",
+                cargo_home.join("registry/src"),
+                CacheLayout::Subdirectories,
+                RiskLevel::Safe,
+                true,
+                None,
+                "Extracted crate sources; cargo re-extracts them from registry/cache on the next build",
+            ));
+            locations.push(package_location(
+                PackageEcosystem::Cargo,
+                "Cargo registry archives",
+                cargo_home.join("registry/cache"),
+                CacheLayout::Files,
+                RiskLevel::Low,
+                false,
+                None,
+                "Downloaded .crate archives; cargo downloads them again when needed",
+            ));
+            locations.push(package_location(
+                PackageEcosystem::Cargo,
+                "Cargo git checkouts",
+                cargo_home.join("git/checkouts"),
+                CacheLayout::Subdirectories,
+                RiskLevel::Safe,
+                true,
+                None,
+                "Working copies of git dependencies; recreated from git/db",
+            ));
+            locations.push(package_location(
+                PackageEcosystem::Cargo,
+                "Cargo git database",
+                cargo_home.join("git/db"),
+                CacheLayout::Subdirectories,
+                RiskLevel::Low,
+                false,
+                None,
+                "Bare clones of git dependencies; cloned again when needed",
+            ));
+        }
+
+        // npm
+        let npm_cache = env_dir("npm_config_cache").or_else(|| {
+            if cfg!(windows) {
+                dirs::data_local_dir().map(|d| d.join("npm-cache"))
+            } else {
+                home.as_ref().map(|h| h.join(".npm"))
+            }
+        });
+        if let Some(npm_cache) = npm_cache {
+            locations.push(package_location(
+                PackageEcosystem::Npm,
+                "npm cache",
+                npm_cache.join("_cacache"),
+                CacheLayout::Files,
+                RiskLevel::Safe,
+                false,
+                None,
+                "Content-addressed package tarballs; npm refetches on demand",
+            ));
+        }
+
+        // pip
+        let pip_cache = env_dir("PIP_CACHE_DIR").or_else(|| {
+            if cfg!(windows) {
+                dirs::data_local_dir().map(|d| d.join("pip/Cache"))
+            } else {
+                cache_home.as_ref().map(|c| c.join("pip"))
+            }
+        });
+        if let Some(pip_cache) = pip_cache {
+            locations.push(package_location(
+                PackageEcosystem::Pip,
+                "pip cache",
+                pip_cache,
+                CacheLayout::Files,
+                RiskLevel::Safe,
+                false,
+                None,
+                "Downloaded wheels and HTTP responses; pip refetches on demand",
+            ));
+        }
+
+        // Go
+        let go_path = std::env::var_os("GOPATH")
+            .and_then(|p| std::env::split_paths(&p).next())
+            .filter(|p| p.is_absolute())
+            .or_else(|| home.as_ref().map(|h| h.join("go")));
+        if let Some(mod_cache) = env_dir("GOMODCACHE").or_else(|| go_path.map(|p| p.join("pkg/mod"))) {
+            locations.push(package_location(
+                PackageEcosystem::Go,
+                "Go module cache",
+                mod_cache,
+                CacheLayout::GoModules,
+                RiskLevel::Low,
+                false,
+                None,
+                "Downloaded module versions; files are read-only, `go clean -modcache` removes them all",
+            ));
+        }
+        if let Some(build_cache) = env_dir("GOCACHE").or_else(|| cache_home.as_ref().map(|c| c.join("go-build"))) {
+            locations.push(package_location(
+                PackageEcosystem::Go,
+                "Go build cache",
+                build_cache,
+                CacheLayout::Files,
+                RiskLevel::Safe,
+                true,
+                None,
+                "Compiled package outputs; rebuilt by the next `go build`",
+            ));
+        }
+
+        // Maven
+        if let Some(ref home) = home {
+            locations.push(package_location(
+                PackageEcosystem::Maven,
+                "Maven local repository",
+                home.join(".m2/repository"),
+                CacheLayout::MavenArtifacts,
+                RiskLevel::Low,
+                false,
+                Some(self.idle_days),
+                "Artifact versions no build has used recently; maven downloads them again when needed",
+            ));
+        }
+
+        // Gradle
+        if let Some(gradle_home) = env_dir("GRADLE_USER_HOME").or_else(|| home.as_ref().map(|h| h.join(".gradle"))) {
+            locations.push(package_location(
+                PackageEcosystem::Gradle,
+                "Gradle dependency cache",
+                gradle_home.join("caches/modules-2/files-2.1"),
+                CacheLayout::GradleArtifacts,
+                RiskLevel::Low,
+                false,
+                Some(self.idle_days),
+                "Dependency versions no build has used recently; gradle downloads them again when needed",
+            ));
+            locations.push(package_location(
+                PackageEcosystem::Gradle,
+                "Gradle build cache",
+                gradle_home.join("caches/build-cache-1"),
+                CacheLayout::Files,
+                RiskLevel::Safe,
+                true,
+                None,
+                "Cached task outputs; rebuilt by the next build",
+            ));
+        }
+
+        // Yarn
+        if let Some(yarn_cache) = env_dir("YARN_CACHE_FOLDER").or_else(|| cache_home.as_ref().map(|c| c.join("yarn"))) {
+            locations.push(package_location(
+                PackageEcosystem::Yarn,
+                "Yarn cache",
+                yarn_cache,
+                CacheLayout::Files,
+                RiskLevel::Safe,
+                false,
+                None,
+                "Package tarballs; yarn refetches on demand",
+            ));
+        }
+        if let Some(ref home) = home {
+            locations.push(package_location(
+                PackageEcosystem::Yarn,
+                "Yarn Berry cache",
+                home.join(".yarn/berry/cache"),
+                CacheLayout::Files,
+                RiskLevel::Safe,
+                false,
+                None,
+                "Package archives of the global Yarn 2+ mirror",
+            ));
+        }
+
+        locations.into_iter().filter(|l| l.location.path.is_dir()).collect()
+    }
+
+    pub fn scan(&self, state: Option<Arc<AppState>>) -> Result<PackageCacheScanResult> {
+        let start = std::time::Instant::now();
+        let mut caches = Vec::new();
+
+        for location in self.get_locations() {
+            if let Some(ref s) = state {
+                if s.is_cancelled() {
+                    return Err(CleanerError::Cancelled);
+                }
+                s.update_progress(crate::state::ScanProgress {
+                    current_path: format!("Scanning: {}", location.location.name),
+                    files_scanned: 0,
+                    bytes_scanned: 0,
+                    files_found: caches.len() as u64,
+                    bytes_found: caches.iter().map(|c: &PackageCacheResult| c.reclaimable_size).sum(),
+                    phase: "package_cache_scan".to_string(),
+                    percentage: 0.0,
+                });
+            }
+
+            caches.push(self.scan_location(&location));
+        }
+
+        Ok(PackageCacheScanResult {
+            total_size: caches.iter().map(|c| c.total_size).sum(),
+            reclaimable_size: caches.iter().map(|c| c.reclaimable_size).sum(),
+            caches,
+            scan_duration_ms: start.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn scan_location(&self, location: &PackageCacheLocation) -> PackageCacheResult {
+        let root = &location.location.path;
+        let local_rules = LocalRules::new();
+        let entry_dirs: Vec<PathBuf> = match location.layout {
+            CacheLayout::Files => vec![],
+            CacheLayout::Subdirectories => read_subdirectories(root, &local_rules),
+            CacheLayout::MavenArtifacts => find_dirs(root, usize::MAX, &local_rules, |dir| {
+                has_file_with_extension(dir, "pom")
+            }),
+            CacheLayout::GradleArtifacts => find_dirs(root, 3, &local_rules, |dir| {
+                dir.strip_prefix(root).map(|rel| rel.components().count() == 3).unwrap_or(false)
+            }),
+            CacheLayout::GoModules => find_dirs(root, usize::MAX, &local_rules, |dir| {
+                dir.file_name()
+                    .map(|n| n.to_string_lossy().contains('@'))
+                    .unwrap_or(false)
+                    && !dir.starts_with(root.join("cache"))
+            }),
+        };
+
+        let mut entries = Vec::new();
+        let mut total_size = 0;
+
+        if location.layout == CacheLayout::Files {
+            // Category and risk come from the location, not the rules
+            let scanner = FileScanner::with_rules(
+                ScanOptions {
+                    include_hidden: true,
+                    exclude_patterns: vec![],
+                    ..Default::default()
+                },
+                CleanupRule::with_browser_caches(&[]),
+            );
+            if let Ok(result) = scanner.scan(&[root.clone()], None) {
+                for mut file in result.files.into_iter().filter(|f| f.file_type == FileType::File) {
+                    total_size += file.size;
+                    file.category = FileCategory::Cache;
+                    file.risk_level = location.risk_level.clone();
+                    file.description = Some(location.description.clone());
+                    entries.push(file);
+                }
+            }
+        } else {
+            let cutoff = location
+                .min_idle_days
+                .map(|days| Utc::now() - chrono::Duration::days(days as i64));
+
+            for dir in entry_dirs {
+                let usage = dir_usage(&dir);
+                total_size += usage.size;
+
+                // atime may be stale on noatime mounts, so the newer of both wins
+                let last_used = usage.last_accessed.max(usage.last_modified);
+                if let (Some(cutoff), Some(last_used)) = (cutoff, last_used) {
+                    if last_used > cutoff {
+                        continue;
+                    }
+                }
+
+                let description = match (location.min_idle_days, last_used) {
+                    (Some(_), Some(last_used)) => format!(
+                        "{} (unused for {} days)",
+                        location.description,
+                        (Utc::now() - last_used).num_days()
+                    ),
+                    _ => location.description.clone(),
+                };
+
+                entries.push(ScannedFile::from_directory(
+                    &dir,
+                    &usage,
+                    FileCategory::Cache,
+                    location.risk_level.clone(),
+                    Some(description),
+                ));
+            }
+        }
+
+        entries.sort_by(|a, b| b.size.cmp(&a.size));
+
+        PackageCacheResult {
+            ecosystem: location.ecosystem.clone(),
+            location: location.location.clone(),
+            risk_level: location.risk_level.clone(),
+            total_size: if location.layout == CacheLayout::Files { total_size } else { dir_usage(root).size },
+            reclaimable_size: entries.iter().map(|e| e.size).sum(),
+            entries,
+        }
+    }
+}
+
+impl Default for PackageCacheScanner {
+    fn default() -> Self {
+        Self::new(DEFAULT_PACKAGE_IDLE_DAYS)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn package_location(
+    ecosystem: PackageEcosystem,
+    name: &str,
+    path: PathBuf,
+    layout: CacheLayout,
+    risk_level: RiskLevel,
+    rebuildable: bool,
+    min_idle_days: Option<u32>,
+    description: &str,
+) -> PackageCacheLocation {
+    PackageCacheLocation {
+        location: CacheLocation {
+            name: name.to_string(),
+            path,
+            category: CacheCategory::Package,
+            safe_to_clean: risk_level == RiskLevel::Safe,
+            owner: Some(format!("{:?}", ecosystem).to_lowercase()),
+        },
+        ecosystem,
+        layout,
+        risk_level,
+        rebuildable,
+        min_idle_days,
+        description: description.to_string(),
+    }
+}
+
+/// Reads an absolute directory from an environment variable.
+fn env_dir(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+}
+
+fn read_subdirectories(dir: &Path, local_rules: &LocalRules) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                .map(|e| e.path())
+                .filter(|path| !local_rules.is_ignored(path, true))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collects the shallowest directories below `root` for which `is_entry`
+/// holds, without descending into them or into ignored directories.
+fn find_dirs(
+    root: &Path,
+    max_depth: usize,
+    local_rules: &LocalRules,
+    is_entry: impl Fn(&Path) -> bool,
+) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let mut walker = WalkDir::new(root)
+        .follow_links(false)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if local_rules.is_ignored(entry.path(), true) {
+            walker.skip_current_dir();
+        } else if is_entry(entry.path()) {
+            found.push(entry.path().to_path_buf());
+            walker.skip_current_dir();
+        }
+    }
+
+    found
+}
+
+fn has_file_with_extension(dir: &Path, extension: &str) -> bool {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .any(|e| e.path().extension().map(|ext| ext == extension).unwrap_or(false))
+        })
+        .unwrap_or(false)
+}
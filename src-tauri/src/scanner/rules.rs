@@ -1,6 +1,6 @@
 // src-tauri/src/scanner/rules.rs
 use super::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -20,7 +20,15 @@ pub struct CleanupRule {
 }
 
 impl CleanupRule {
+    /// Discovers browser profiles to fill in the browser cache rule; for
+    /// repeated use, discover once and call `with_browser_caches`.
     pub fn default_rules() -> Vec<Self> {
+        Self::with_browser_caches(&BrowserScanner::cache_directories())
+    }
+
+    /// The default rules, with `browser_caches` as the browser cache rule's
+    /// directories.
+    pub fn with_browser_caches(browser_caches: &[PathBuf]) -> Vec<Self> {
         let mut rules = vec![
             Self {
                 id: "temp_files".to_string(),
@@ -57,7 +65,10 @@ impl CleanupRule {
                 category: FileCategory::BrowserCache,
                 patterns: vec![],
                 extensions: vec![],
-                directories: vec![], // Will be populated dynamically
+                directories: browser_caches
+                    .iter()
+                    .map(|d| d.to_string_lossy().to_string())
+                    .collect(),
                 min_age_days: Some(7),
                 min_size_bytes: None,
                 max_size_bytes: None,
@@ -78,6 +89,22 @@ impl CleanupRule {
                 description: "Remove thumbnail cache files".to_string(),
                 risk_level: RiskLevel::Safe,
             },
+            Self {
+                id: "old_downloads".to_string(),
+                name: "Old Downloads".to_string(),
+                enabled: true,
+                category: FileCategory::Download,
+                patterns: vec![],
+                extensions: vec![],
+                directories: dirs::download_dir()
+                    .map(|d| vec![d.to_string_lossy().to_string()])
+                    .unwrap_or_default(),
+                min_age_days: Some(30),
+                min_size_bytes: None,
+                max_size_bytes: None,
+                description: "Downloads that have not been modified in a month".to_string(),
+                risk_level: RiskLevel::Medium,
+            },
         ];
 
         // Add platform specific directories to rules
@@ -115,6 +142,33 @@ impl RuleEngine {
     }
 
     pub fn categorize(&self, path: &Path, metadata: &std::fs::Metadata) -> (FileCategory, RiskLevel, Option<String>) {
+        self.categorize_excluding(path, metadata, &[])
+    }
+
+    /// Same as `categorize`, but skips the rules whose ids are listed in `disabled`.
+    pub fn categorize_excluding(
+        &self,
+        path: &Path,
+        metadata: &std::fs::Metadata,
+        disabled: &[String],
+    ) -> (FileCategory, RiskLevel, Option<String>) {
+        match self.match_rule_excluding(path, metadata, disabled) {
+            Some(rule) => (rule.category.clone(), rule.risk_level.clone(), Some(rule.description.clone())),
+            None => (FileCategory::Unknown, RiskLevel::Low, None),
+        }
+    }
+
+    /// The first enabled rule that matches `path`.
+    pub fn match_rule(&self, path: &Path, metadata: &std::fs::Metadata) -> Option<&CleanupRule> {
+        self.match_rule_excluding(path, metadata, &[])
+    }
+
+    fn match_rule_excluding(
+        &self,
+        path: &Path,
+        metadata: &std::fs::Metadata,
+        disabled: &[String],
+    ) -> Option<&CleanupRule> {
         let path_str = path.to_string_lossy().to_lowercase();
         let file_name = path.file_name()
             .map(|n| n.to_string_lossy().to_lowercase())
@@ -122,6 +176,7 @@ impl RuleEngine {
 
         for rule in &self.rules {
             if !rule.enabled { continue; }
+            if disabled.contains(&rule.id) { continue; }
 
             let mut matched = false;
             
@@ -163,10 +218,10 @@ impl RuleEngine {
                         }
                     }
                 }
-                return (rule.category.clone(), rule.risk_level.clone(), Some(rule.description.clone()));
+                return Some(rule);
             }
         }
 
-        (FileCategory::Unknown, RiskLevel::Low, None)
+        None
     }
 }
@@ -6,12 +6,15 @@ pub mod windows;
 #[cfg(target_os = "macos")]
 pub mod macos;
 
+use crate::paths::serde_path;
+use crate::scanner::RiskLevel;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartupItem {
     pub name: String,
+    #[serde(with = "serde_path")]
     pub path: PathBuf,
     pub command: Option<String>,
     pub enabled: bool,
@@ -19,6 +22,17 @@ pub struct StartupItem {
     pub description: Option<String>,
 }
 
+impl StartupItem {
+    /// Entries that start for every user or run as services are high risk
+    /// to remove; the user's own are medium.
+    pub fn risk_level(&self) -> RiskLevel {
+        match self.source {
+            StartupSource::System | StartupSource::Service => RiskLevel::High,
+            StartupSource::User | StartupSource::Registry | StartupSource::LaunchAgent => RiskLevel::Medium,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum StartupSource {
     User,
@@ -1,4 +1,5 @@
 // src-tauri/src/state.rs
+use crate::cleanup::ConfirmationStore;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
@@ -16,11 +17,16 @@ pub struct ScanProgress {
     pub percentage: f32,
 }
 
+/// Cheap to clone: clones share the cancellation flag and progress, so a
+/// clone can be moved into a blocking task.
+#[derive(Clone)]
 pub struct AppState {
     pub db_path: PathBuf,
-    pub scan_cancelled: AtomicBool,
-    pub scan_progress: RwLock<ScanProgress>,
+    pub scan_cancelled: Arc<AtomicBool>,
+    pub scan_progress: Arc<RwLock<ScanProgress>>,
     pub progress_sender: broadcast::Sender<ScanProgress>,
+    /// Tokens for irreversible commands; see `ConfirmationStore`.
+    pub confirmations: Arc<ConfirmationStore>,
 }
 
 impl AppState {
@@ -28,8 +34,8 @@ impl AppState {
         let (tx, _) = broadcast::channel(100);
         Self {
             db_path,
-            scan_cancelled: AtomicBool::new(false),
-            scan_progress: RwLock::new(ScanProgress {
+            scan_cancelled: Arc::new(AtomicBool::new(false)),
+            scan_progress: Arc::new(RwLock::new(ScanProgress {
                 current_path: String::new(),
                 files_scanned: 0,
                 bytes_scanned: 0,
@@ -37,8 +43,9 @@ impl AppState {
                 bytes_found: 0,
                 phase: "idle".to_string(),
                 percentage: 0.0,
-            }),
+            })),
             progress_sender: tx,
+            confirmations: Arc::new(ConfirmationStore::new()),
         }
     }
 
@@ -1,4 +1,6 @@
 // src-tauri/src/system/mod.rs
+pub mod mounts;
+
 use serde::{Deserialize, Serialize};
 use sysinfo::{System, SystemExt, CpuExt, DiskExt, ProcessExt, Disks};
 
@@ -24,6 +26,8 @@ pub struct DiskInfo {
     pub usage_percent: f32,
     pub file_system: String,
     pub is_removable: bool,
+    /// Bytes held by quarantined files on this disk, freed when they are purged.
+    pub pending_reclaim: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,6 +90,7 @@ pub fn get_disk_usage() -> Vec<DiskInfo> {
             usage_percent,
             file_system: disk.file_system().to_string_lossy().to_string(),
             is_removable: disk.is_removable(),
+            pending_reclaim: 0,
         }
     }).collect()
 }
@@ -0,0 +1,110 @@
+// src-tauri/src/system/mounts.rs
+use crate::paths::serde_path;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MountInfo {
+    pub device: String,
+    #[serde(with = "serde_path")]
+    pub mount_point: PathBuf,
+    pub file_system: String,
+    pub options: Vec<String>,
+}
+
+impl MountInfo {
+    pub fn has_option(&self, option: &str) -> bool {
+        self.options.iter().any(|o| o == option)
+    }
+
+    /// With `noatime` the kernel never updates access times, so atime says
+    /// nothing about whether a file was opened. `relatime` still updates
+    /// atime when it is older than mtime, which is all we compare.
+    pub fn tracks_access_time(&self) -> bool {
+        !self.has_option("noatime")
+    }
+}
+
+/// Mounted filesystems from `/proc/self/mounts`. Empty on other platforms.
+pub fn read_mounts() -> Vec<MountInfo> {
+    let Ok(content) = fs::read_to_string("/proc/self/mounts") else {
+        return vec![];
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = unescape_mount_field(fields.next()?);
+            let mount_point = PathBuf::from(unescape_mount_field(fields.next()?));
+            let file_system = fields.next()?.to_string();
+            let options = fields.next()?.split(',').map(String::from).collect();
+            Some(MountInfo {
+                device,
+                mount_point,
+                file_system,
+                options,
+            })
+        })
+        .collect()
+}
+
+/// The mount that contains `path`, i.e. the one with the longest matching
+/// mount point. Later entries win, since they shadow earlier ones.
+pub fn mount_for(path: &Path) -> Option<MountInfo> {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+    read_mounts()
+        .into_iter()
+        .filter(|m| path.starts_with(&m.mount_point))
+        .fold(None, |best: Option<MountInfo>, m| match best {
+            Some(b) if b.mount_point.as_os_str().len() > m.mount_point.as_os_str().len() => Some(b),
+            _ => Some(m),
+        })
+}
+
+/// Bytes an unprivileged user can still write on the filesystem holding
+/// `path` (`f_bavail`), which leaves the blocks reserved for root alone.
+#[cfg(unix)]
+pub fn available_space(path: &Path) -> io::Result<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn available_space(_path: &Path) -> io::Result<u64> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "statvfs is not available on this platform"))
+}
+
+/// The kernel escapes space, tab, newline and backslash as `\ooo` octal.
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() {
+            if let Some(byte) = std::str::from_utf8(&bytes[i + 1..i + 4])
+                .ok()
+                .and_then(|o| u8::from_str_radix(o, 8).ok())
+            {
+                out.push(byte);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).to_string()
+}
@@ -1,13 +1,36 @@
 // src/components/views/Settings.tsx
-import React from 'react';
+import React, { useEffect, useState } from 'react';
 import { useAppStore } from '../../stores/appStore';
 import { Card, CardHeader, CardTitle, CardDescription } from '../ui/Card';
 import { Toggle } from '../ui/Toggle';
 import { Button } from '../ui/Button';
-import { RefreshCw } from 'lucide-react';
+import { RefreshCw, Archive } from 'lucide-react';
+import * as api from '../../utils/api';
+import { formatBytes, formatConfirmation, formatRelativeTime } from '../../utils/format';
+import { BackupUsage } from '../../types';
 
 export function SettingsView() {
     const { settings, updateSettings, reset } = useAppStore();
+    const [backupUsage, setBackupUsage] = useState<BackupUsage | null>(null);
+    const [collecting, setCollecting] = useState(false);
+
+    useEffect(() => {
+        api.getBackupUsage().then(setBackupUsage).catch(() => setBackupUsage(null));
+    }, []);
+
+    const handleBackupGc = async () => {
+        setCollecting(true);
+        try {
+            const summary = await api.prepareBackupGc();
+            if (summary.count > 0 && !window.confirm(formatConfirmation(summary, 'Remove the backups of'))) {
+                return;
+            }
+            const result = await api.runBackupGc(false, summary.token);
+            setBackupUsage(result.usage);
+        } finally {
+            setCollecting(false);
+        }
+    };
 
     return (
         <div className="max-w-3xl mx-auto space-y-6">
@@ -95,6 +118,34 @@ export function SettingsView() {
                 </div>
             </Card>
 
+            <Card>
+                <CardHeader>
+                    <CardTitle>Restore Point Backups</CardTitle>
+                    <CardDescription>Old restore points expire by age, count and total size</CardDescription>
+                </CardHeader>
+                <div className="flex items-center justify-between">
+                    <div>
+                        <p className="font-medium">
+                            {backupUsage ? formatBytes(backupUsage.stored_bytes) : '—'} used
+                        </p>
+                        <p className="text-sm text-dark-500">
+                            {backupUsage
+                                ? `${backupUsage.restore_points} restore points, ${formatBytes(backupUsage.logical_bytes)} of files` +
+                                  (backupUsage.oldest ? `, oldest ${formatRelativeTime(backupUsage.oldest)}` : '')
+                                : 'Backup usage unavailable'}
+                        </p>
+                    </div>
+                    <Button
+                        variant="ghost"
+                        onClick={handleBackupGc}
+                        isLoading={collecting}
+                        leftIcon={<Archive className="w-4 h-4" />}
+                    >
+                        Clean Up Backups
+                    </Button>
+                </div>
+            </Card>
+
             <Card>
                 <CardHeader>
                     <CardTitle>Thresholds</CardTitle>
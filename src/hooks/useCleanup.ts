@@ -2,7 +2,8 @@
 import { useCallback, useEffect, useState } from 'react';
 import { useAppStore } from '../stores/appStore';
 import * as api from '../utils/api';
-import { formatBytes } from '../utils/format';
+import { formatBytes, formatConfirmation } from '../utils/format';
+import type { FileIdentity, RawPath, ScanResult } from '../types';
 
 interface CleanupProgress {
     current: number;
@@ -10,6 +11,11 @@ interface CleanupProgress {
     currentFile: string;
 }
 
+/** Pairs each path with its scan-time identity for the backend to check. */
+function identitiesOf(items: ScanResult[]): [RawPath, FileIdentity][] {
+    return items.flatMap((i) => (i.identity ? [[i.path, i.identity] as [RawPath, FileIdentity]] : []));
+}
+
 export function useCleanup() {
     const {
         scanResults,
@@ -18,7 +24,6 @@ export function useCleanup() {
         settings,
         setIsCleaning,
         setCurrentSession,
-        addRestorePoint,
         addNotification,
         deselectAllResults,
     } = useAppStore();
@@ -51,23 +56,34 @@ export function useCleanup() {
         setIsCleaning(true);
         setCleanupProgress({ current: 0, total: selectedItems.length, currentFile: '' });
 
+        const startTime = new Date().toISOString();
         try {
-            // Create restore point if enabled
-            if (settings.createRestorePoints && !isDryRun) {
-                const restorePoint = await api.createRestorePoint(
-                    selectedItems.map((i) => i.id),
-                    `Cleanup session - ${new Date().toLocaleString()}`
-                );
-                addRestorePoint(restorePoint);
+            const paths = selectedItems.map((i) => i.path);
+            const identities = identitiesOf(selectedItems);
+            let result;
+            if (isDryRun) {
+                result = await api.deleteFilesDryRun(paths, identities);
+            } else if (settings.secureDelete) {
+                const summary = await api.prepareDelete(paths, true);
+                if (!window.confirm(formatConfirmation(summary, 'Securely delete'))) {
+                    return;
+                }
+                // Confirming the dialog above acknowledges any high-risk items it listed
+                result = await api.secureDelete(paths, summary.token, summary.requires_acknowledgement, identities);
+            } else {
+                result = await api.deleteFiles(paths, true, settings.createRestorePoints, identities);
             }
 
-            const session = await api.performCleanup(
-                selectedItems.map((i) => i.id),
-                isDryRun,
-                settings.secureDelete
-            );
-
-            setCurrentSession(session);
+            setCurrentSession({
+                id: String(result.operation_id ?? startTime),
+                startTime,
+                endTime: new Date().toISOString(),
+                filesScanned: selectedItems.length,
+                filesDeleted: result.deleted_files.length,
+                spaceFreed: result.bytes_freed,
+                status: 'completed',
+                items: [],
+            });
             deselectAllResults();
 
             addNotification({
@@ -75,7 +91,7 @@ export function useCleanup() {
                 title: isDryRun ? 'Dry Run Complete' : 'Cleanup Complete',
                 message: isDryRun
                     ? `Would free ${formatBytes(selectedSize)} from ${selectedItems.length} items`
-                    : `Freed ${formatBytes(session.spaceFreed)} from ${session.filesDeleted} files`,
+                    : `Freed ${formatBytes(result.bytes_freed)} from ${result.deleted_files.length} files`,
             });
         } catch (error) {
             addNotification({
@@ -93,7 +109,6 @@ export function useCleanup() {
         settings,
         setIsCleaning,
         setCurrentSession,
-        addRestorePoint,
         addNotification,
         deselectAllResults,
         selectedSize
@@ -11,6 +11,7 @@ export interface ScanResult {
     isSelected: boolean;
     risk: RiskLevel;
     description: string;
+    identity?: FileIdentity;
 }
 
 export type FileType =
@@ -39,6 +40,32 @@ export type CleanupCategory =
 
 export type RiskLevel = 'safe' | 'low' | 'medium' | 'high';
 
+/**
+ * A path as the backend sends it: a plain string when it is valid UTF-8,
+ * otherwise its raw bytes plus a lossy form for display. Show it with
+ * `formatPath` and pass it back to the backend unchanged.
+ */
+export type RawPath = string | { display: string; raw: number[] };
+
+/**
+ * Device and inode a file had when it was scanned. Sent back with the path
+ * so the backend skips anything replaced since.
+ */
+export interface FileIdentity {
+    device: number;
+    inode: number;
+}
+
+export interface DeleteResult {
+    deleted_files: RawPath[];
+    failed_files: [RawPath, string][];
+    logical_bytes: number;
+    bytes_freed: number;
+    operation_id: number | null;
+    warnings: string[];
+    was_dry_run: boolean;
+}
+
 export interface DuplicateGroup {
     hash: string;
     files: DuplicateFile[];
@@ -134,6 +161,130 @@ export interface RestoreItem {
     restored: boolean;
 }
 
+export interface BackupUsage {
+    restore_points: number;
+    objects: number;
+    logical_bytes: number;
+    stored_bytes: number;
+    oldest: string | null;
+}
+
+export interface BackupGcResult {
+    dry_run: boolean;
+    expired: { id: number; original_path: RawPath; created_at: string | null; reason: 'Age' | 'Count' | 'Size' }[];
+    freed_bytes: number;
+    usage: BackupUsage;
+}
+
+export interface FreeSpaceWipeOptions {
+    safety_margin_bytes: number;
+    random_data: boolean;
+}
+
+export interface FreeSpaceWipeResult {
+    mount_point: RawPath;
+    bytes_written: number;
+    files_created: number;
+    available_before: number;
+    warnings: string[];
+}
+
+export interface AuditVerification {
+    entries: number;
+    valid: boolean;
+    first_invalid_line: number | null;
+    problem: string | null;
+    last_hash: string | null;
+    repairs: number[];
+}
+
+export interface HistoryFileRow {
+    history_id: number;
+    timestamp: string;
+    operation_type: string;
+    path: RawPath;
+    category: string;
+    rule_id: string | null;
+    bytes: number;
+}
+
+export type ReclaimedGrouping = 'Week' | 'Month' | 'Category' | 'Rule';
+
+export interface ReclaimedSpace {
+    key: string;
+    bytes: number;
+    files: number;
+    cleanups: number;
+}
+
+export interface JunkSource {
+    source: RawPath;
+    cleanups: number;
+    files: number;
+    bytes: number;
+    last_cleaned: string;
+}
+
+export interface QuarantinedItem {
+    id: number;
+    operation_id: number;
+    original_path: RawPath;
+    quarantine_path: RawPath;
+    size: number;
+    quarantined_at: string | null;
+    purge_after: string | null;
+}
+
+export interface QuarantinePurgeResult {
+    purged: QuarantinedItem[];
+    failed: [RawPath, string][];
+    bytes_freed: number;
+    was_dry_run: boolean;
+}
+
+export interface TrashItem {
+    name: string;
+    original_path: RawPath;
+    deleted_at: string | null;
+    size: number;
+    is_dir: boolean;
+    files_path: RawPath;
+    info_path: RawPath;
+}
+
+export interface TrashPurgeResult {
+    purged: TrashItem[];
+    orphaned_info: RawPath[];
+    failed: [RawPath, string][];
+    bytes_freed: number;
+    was_dry_run: boolean;
+}
+
+export type IrreversibleAction =
+    | 'Delete'
+    | 'SecureDelete'
+    | 'RemoveStartupItem'
+    | 'PurgeTrash'
+    | 'PurgeQuarantine'
+    | 'WipeFreeSpace'
+    | 'BackupGc';
+
+/**
+ * Returned by the `prepare*` calls. Pass `token`
+ * to the matching call for the same paths before `expires_at`; when
+ * `requires_acknowledgement` is set, that call also needs
+ * `acknowledgeHighRisk`.
+ */
+export interface ConfirmationSummary {
+    token: string;
+    action: IrreversibleAction;
+    count: number;
+    bytes: number;
+    highest_risk: 'Safe' | 'Low' | 'Medium' | 'High';
+    requires_acknowledgement: boolean;
+    expires_at: string;
+}
+
 export interface ScanProgress {
     phase: 'initializing' | 'scanning' | 'analyzing' | 'completed';
     currentPath: string;
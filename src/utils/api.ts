@@ -8,9 +8,25 @@ import type {
     SystemHealth,
     CleanupSession,
     RestorePoint,
+    BackupUsage,
+    BackupGcResult,
+    FreeSpaceWipeOptions,
+    FreeSpaceWipeResult,
+    AuditVerification,
+    HistoryFileRow,
+    ReclaimedGrouping,
+    ReclaimedSpace,
+    JunkSource,
+    QuarantinedItem,
+    QuarantinePurgeResult,
     ScanProgress,
     LargeFile,
-    CleanupCategory
+    CleanupCategory,
+    ConfirmationSummary,
+    DeleteResult,
+    TrashPurgeResult,
+    FileIdentity,
+    RawPath
 } from '../types';
 
 // System Health
@@ -40,12 +56,20 @@ export function onScanComplete(callback: (results: ScanResult[]) => void) {
 }
 
 // Cleanup
-export async function performCleanup(
-    items: string[],
-    dryRun: boolean,
-    secureDelete: boolean
-): Promise<CleanupSession> {
-    return invoke('perform_cleanup', { items, dryRun, secureDelete });
+export async function deleteFiles(
+    paths: RawPath[],
+    useTrash: boolean,
+    createRestorePoint: boolean,
+    identities?: [RawPath, FileIdentity][]
+): Promise<DeleteResult> {
+    return invoke('delete_files', { paths, useTrash, createRestorePoint, identities });
+}
+
+export async function deleteFilesDryRun(
+    paths: RawPath[],
+    identities?: [RawPath, FileIdentity][]
+): Promise<DeleteResult> {
+    return invoke('delete_files_dry_run', { paths, identities });
 }
 
 export async function cancelCleanup(): Promise<void> {
@@ -100,6 +124,18 @@ export async function toggleStartupItem(
     return invoke('toggle_startup_item', { id, enabled });
 }
 
+export async function prepareRemoveStartupItem(name: string): Promise<ConfirmationSummary> {
+    return invoke('prepare_remove_startup_item', { name });
+}
+
+export async function removeStartupItem(
+    name: string,
+    confirmation: string,
+    acknowledgeHighRisk = false
+): Promise<boolean> {
+    return invoke('remove_startup_item', { name, confirmation, acknowledgeHighRisk });
+}
+
 export async function deleteStartupItem(id: string): Promise<boolean> {
     return invoke('delete_startup_item', { id });
 }
@@ -127,17 +163,125 @@ export async function deleteRestorePoint(pointId: string): Promise<boolean> {
     return invoke('delete_restore_point', { pointId });
 }
 
+export async function getBackupUsage(): Promise<BackupUsage> {
+    return invoke('get_backup_usage');
+}
+
+export async function prepareBackupGc(): Promise<ConfirmationSummary> {
+    return invoke('prepare_backup_gc');
+}
+
+/** Outside a dry run, `confirmation` is the token from `prepareBackupGc`. */
+export async function runBackupGc(dryRun = false, confirmation?: string): Promise<BackupGcResult> {
+    return invoke('run_backup_gc', { dryRun, confirmation });
+}
+
+export async function prepareWipeFreeSpace(mountPoint: string): Promise<ConfirmationSummary> {
+    return invoke('prepare_wipe_free_space', { mountPoint });
+}
+
+export async function wipeFreeSpace(
+    mountPoint: string,
+    confirmation: string,
+    options?: FreeSpaceWipeOptions
+): Promise<FreeSpaceWipeResult> {
+    return invoke('wipe_free_space', { mountPoint, options, confirmation });
+}
+
+// Quarantine
+export async function quarantineFiles(
+    paths: RawPath[],
+    identities?: [RawPath, FileIdentity][]
+): Promise<DeleteResult> {
+    return invoke('quarantine_files', { paths, identities });
+}
+
+export async function getQuarantine(): Promise<QuarantinedItem[]> {
+    return invoke('get_quarantine');
+}
+
+export async function restoreQuarantined(
+    ids: number[]
+): Promise<{ files: { id: number; restored_to: RawPath | null }[]; failed: [RawPath, string][] }> {
+    return invoke('restore_quarantined', { ids });
+}
+
+export async function preparePurgeQuarantine(): Promise<ConfirmationSummary> {
+    return invoke('prepare_purge_quarantine');
+}
+
+/** With `all` outside a dry run, `confirmation` is the token from `preparePurgeQuarantine`. */
+export async function purgeQuarantine(
+    dryRun = false,
+    all = false,
+    confirmation?: string
+): Promise<QuarantinePurgeResult> {
+    return invoke('purge_quarantine', { dryRun, all, confirmation });
+}
+
+// Cleanup History
+export async function getCleanupHistoryFiles(historyId: number): Promise<HistoryFileRow[]> {
+    return invoke('get_cleanup_history_files', { historyId });
+}
+
+export async function getReclaimedSpace(groupBy: ReclaimedGrouping, since?: string): Promise<ReclaimedSpace[]> {
+    return invoke('get_reclaimed_space', { groupBy, since });
+}
+
+export async function getJunkSources(limit?: number): Promise<JunkSource[]> {
+    return invoke('get_junk_sources', { limit });
+}
+
+export async function exportCleanupHistory(
+    destination: string,
+    format: 'Csv' | 'Json',
+    since?: string
+): Promise<number> {
+    return invoke('export_cleanup_history', { destination, format, since });
+}
+
+// Audit Log
+export async function verifyAuditLog(): Promise<AuditVerification> {
+    return invoke('verify_audit_log');
+}
+
+export async function exportAuditLog(destination: string, from?: string, to?: string): Promise<number> {
+    return invoke('export_audit_log', { destination, from, to });
+}
+
 // File Operations
 export async function openInExplorer(path: string): Promise<void> {
     return invoke('open_in_explorer', { path });
 }
 
-export async function moveToTrash(paths: string[]): Promise<number> {
+export async function moveToTrash(paths: RawPath[]): Promise<number> {
     return invoke('move_to_trash', { paths });
 }
 
-export async function secureDelete(paths: string[]): Promise<number> {
-    return invoke('secure_delete', { paths });
+export async function preparePurgeTrash(olderThanDays: number): Promise<ConfirmationSummary> {
+    return invoke('prepare_purge_trash', { olderThanDays });
+}
+
+/** Outside a dry run, `confirmation` is the token from `preparePurgeTrash`. */
+export async function purgeTrash(
+    olderThanDays: number,
+    dryRun = false,
+    confirmation?: string
+): Promise<TrashPurgeResult> {
+    return invoke('purge_trash', { olderThanDays, dryRun, confirmation });
+}
+
+export async function prepareDelete(paths: RawPath[], secure = false): Promise<ConfirmationSummary> {
+    return invoke('prepare_delete', { paths, secure });
+}
+
+export async function secureDelete(
+    paths: RawPath[],
+    confirmation: string,
+    acknowledgeHighRisk = false,
+    identities?: [RawPath, FileIdentity][]
+): Promise<DeleteResult> {
+    return invoke('secure_delete', { paths, identities, confirmation, acknowledgeHighRisk });
 }
 
 // Settings
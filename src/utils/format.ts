@@ -1,4 +1,6 @@
 // src/utils/format.ts
+import type { ConfirmationSummary, RawPath } from '../types';
+
 export function formatBytes(bytes: number, decimals = 2): string {
     if (bytes === 0) return '0 B';
     const k = 1024;
@@ -11,6 +13,15 @@ export function formatNumber(num: number): string {
     return new Intl.NumberFormat().format(num);
 }
 
+/** The question to ask before passing a confirmation token on. */
+export function formatConfirmation(summary: ConfirmationSummary, verb: string): string {
+    const items = `${formatNumber(summary.count)} item${summary.count === 1 ? '' : 's'}`;
+    const question = `${verb} ${items} (${formatBytes(summary.bytes)})? This cannot be undone.`;
+    return summary.requires_acknowledgement
+        ? `${question}\n\nSome of them are high risk: protected, or outside the home and temp directories with no cleanup rule for them.`
+        : question;
+}
+
 export function formatDate(date: string | Date): string {
     return new Intl.DateTimeFormat('en-US', {
         year: 'numeric',
@@ -113,3 +124,8 @@ export function getFileIcon(extension: string): string {
     };
     return iconMap[extension] || iconMap.default;
 }
+
+/** Display form of a path; the raw bytes stay with the value itself. */
+export function formatPath(path: RawPath): string {
+    return typeof path === 'string' ? path : path.display;
+}